@@ -1,5 +1,16 @@
-use crate::modules::screen_server::ScreenServer;
+use winit::dpi::PhysicalSize;
 
+use crate::{modules::screen_server::ScreenServer, EngineInternal};
+
+#[allow(unused_variables)]
 pub trait App {
+    // called before any gpu device exists
     fn start(&mut self, screen_server: &mut ScreenServer);
+    // called once, as soon as the gpu device and window are available
+    fn on_engine_ready(&mut self, engine_internal: &mut EngineInternal) {}
+    fn on_resize(&mut self, engine_internal: &mut EngineInternal, size: PhysicalSize<u32>) {}
+    fn on_focus_changed(&mut self, engine_internal: &mut EngineInternal, focused: bool) {}
+    fn on_suspend(&mut self, engine_internal: &mut EngineInternal) {}
+    fn on_resume(&mut self, engine_internal: &mut EngineInternal) {}
+    fn on_exit(&mut self, engine_internal: &mut EngineInternal) {}
 }
@@ -1,2 +1,6 @@
 pub mod camera;
-pub mod speed;
+pub mod kinematics;
+pub mod transform;
+pub mod hierarchy;
+pub mod renderable;
+pub mod prefab;
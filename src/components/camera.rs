@@ -1,9 +1,12 @@
 use bevy_ecs::prelude::*;
 use cgmath::{Matrix4, Point3, SquareMatrix, Vector3};
+use serde::{Deserialize, Serialize};
+
+use crate::render::camera::view_projection;
 
 pub type CameraUniform = [[f32;4];4];
 
-#[derive(Component)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct CameraComponent {
     pub target: Point3<f32>,
     pub aspect: f32,
@@ -19,6 +22,10 @@ pub struct CameraComponent {
     pub speed: f32,
 }
 
+// the camera whose view projection is uploaded to the default pipeline
+#[derive(Component, Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct ActiveCamera;
+
 impl CameraComponent {
     pub fn debug(config: &wgpu::SurfaceConfiguration) -> Self {
         Self {
@@ -36,4 +43,15 @@ impl CameraComponent {
             view_proj: Matrix4::identity().into(),
         }
     }
+
+    pub fn uniform(&self) -> CameraUniform {
+        view_projection(self.position,
+            self.target,
+            self.up,
+            self.fovy,
+            self.aspect,
+            self.znear,
+            self.zfar
+        )
+    }
 }
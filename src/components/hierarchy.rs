@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use bevy_ecs::prelude::*;
+
+// inserting, changing or removing it is all it takes to reparent an entity
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parent(pub Entity);
+
+impl Parent {
+    pub fn get(&self) -> Entity {
+        self.0
+    }
+}
+
+// kept in sync with the parent components by the engine
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Children(Vec<Entity>);
+
+impl Children {
+    pub fn entities(&self) -> &[Entity] {
+        &self.0
+    }
+}
+
+pub fn sync_children(mut commands: Commands,
+    parents: Query<(Entity, &Parent)>,
+    mut children: Query<(Entity, &mut Children)>,
+) {
+    let mut children_map = HashMap::<Entity, Vec<Entity>>::new();
+    for (entity, parent) in &parents {
+        children_map.entry(parent.get())
+            .or_default()
+            .push(entity);
+    }
+
+    // query order can change as entities move between tables
+    children_map.values_mut()
+        .for_each(|vec| vec.sort());
+
+    for (entity, mut entity_children) in &mut children {
+        match children_map.remove(&entity) {
+            Some(vec) => {
+                if entity_children.0 != vec {
+                    entity_children.0 = vec;
+                }
+            },
+            None => {
+                commands.entity(entity)
+                    .remove::<Children>();
+            },
+        }
+    }
+
+    for (parent, vec) in children_map {
+        // the parent could have been despawned
+        if let Some(mut entity_commands) = commands.get_entity(parent) {
+            entity_commands.insert(Children(vec));
+        }
+    }
+}
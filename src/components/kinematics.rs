@@ -0,0 +1,149 @@
+use bevy_ecs::{prelude::*, schedule::SystemConfigs};
+use cgmath::{InnerSpace, Quaternion, Rad, Rotation3, Vector3, Zero};
+use serde::{Deserialize, Serialize};
+
+use crate::modules::time::Time;
+
+use super::transform::{Transform, TransformSystems};
+
+// in units per second
+#[derive(Component, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Velocity {
+    pub linear: Vector3<f32>,
+    // the fraction of the velocity lost every second
+    pub damping: f32,
+    pub max_speed: Option<f32>,
+}
+
+impl Default for Velocity {
+    fn default() -> Self {
+        Self::new(Vector3::zero())
+    }
+}
+
+impl Velocity {
+    pub fn new(linear: Vector3<f32>) -> Self {
+        Self {
+            linear,
+            damping: 0.0,
+            max_speed: None,
+        }
+    }
+
+    pub fn with_damping(mut self, damping: f32) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    pub fn with_max_speed(mut self, max_speed: f32) -> Self {
+        self.max_speed = Some(max_speed);
+        self
+    }
+}
+
+// the axis of rotation, scaled by radians per second
+#[derive(Component, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AngularVelocity {
+    pub angular: Vector3<f32>,
+    pub damping: f32,
+    pub max_speed: Option<f32>,
+}
+
+impl Default for AngularVelocity {
+    fn default() -> Self {
+        Self::new(Vector3::zero())
+    }
+}
+
+impl AngularVelocity {
+    pub fn new(angular: Vector3<f32>) -> Self {
+        Self {
+            angular,
+            damping: 0.0,
+            max_speed: None,
+        }
+    }
+
+    pub fn with_damping(mut self, damping: f32) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    pub fn with_max_speed(mut self, max_speed: f32) -> Self {
+        self.max_speed = Some(max_speed);
+        self
+    }
+}
+
+// in units per second squared, applied to the velocity
+#[derive(Component, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Acceleration(pub Vector3<f32>);
+
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KinematicsSystems {
+    Integrate,
+}
+
+// run every tick, so that transforms move before they are propagated
+pub fn kinematics_systems() -> SystemConfigs {
+    (accelerate, integrate_velocity, integrate_angular_velocity)
+        .chain()
+        .in_set(KinematicsSystems::Integrate)
+        .before(TransformSystems::Propagate)
+}
+
+pub fn accelerate(mut query: Query<(&mut Velocity, &Acceleration)>, time: Res<Time>) {
+    let delta = time.fixed_delta();
+
+    for (mut velocity, acceleration) in &mut query {
+        velocity.linear += acceleration.0 * delta;
+    }
+}
+
+pub fn integrate_velocity(mut query: Query<(&mut Transform, &mut Velocity)>, time: Res<Time>) {
+    let delta = time.fixed_delta();
+
+    for (mut transform, mut velocity) in &mut query {
+        velocity.linear = limit(velocity.linear, velocity.damping, velocity.max_speed, delta);
+        transform.translation += velocity.linear * delta;
+    }
+}
+
+pub fn integrate_angular_velocity(mut query: Query<(&mut Transform, &mut AngularVelocity)>,
+    time: Res<Time>,
+) {
+    let delta = time.fixed_delta();
+
+    for (mut transform, mut angular_velocity) in &mut query {
+        angular_velocity.angular = limit(angular_velocity.angular,
+            angular_velocity.damping,
+            angular_velocity.max_speed,
+            delta
+        );
+
+        let speed = angular_velocity.angular.magnitude();
+        if speed == 0.0 {
+            continue;
+        }
+
+        let axis = angular_velocity.angular / speed;
+        let rotation = Quaternion::from_axis_angle(axis, Rad(speed * delta));
+        transform.rotation = (rotation * transform.rotation).normalize();
+    }
+}
+
+// damping is applied per second, regardless of the fixed delta
+fn limit(velocity: Vector3<f32>,
+    damping: f32,
+    max_speed: Option<f32>,
+    delta: f32,
+) -> Vector3<f32> {
+    let velocity = velocity * (1.0 - damping.clamp(0.0, 1.0)).powf(delta);
+
+    match max_speed {
+        Some(max_speed) if velocity.magnitude() > max_speed => velocity.normalize_to(max_speed),
+        _ => velocity,
+    }
+}
@@ -0,0 +1,18 @@
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// added to the root of every spawned prefab
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PrefabInstance {
+    pub prefab: String,
+}
+
+impl PrefabInstance {
+    pub fn new(prefab: &str) -> Self {
+        let prefab = prefab.to_string();
+
+        Self {
+            prefab,
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::modules::render_storage::{MaterialId, MeshId};
+
+// a mesh pushed to the engine's render storage, drawn
+// at the entity's global transform while it is visible
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MeshHandle(pub MeshId);
+
+// replaces the material the mesh was pushed with
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MaterialHandle(pub MaterialId);
+
+// entities without it are visible
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Visibility {
+    #[default]
+    Visible,
+    Hidden,
+}
+
+// a mesh of a model loaded through the asset server. it is pushed
+// to the engine's render storage and given a mesh handle before
+// the entity is first drawn, so it can be created without a gpu
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MeshRef {
+    pub file_name: String,
+    #[serde(default)]
+    pub mesh_index: usize,
+}
+
+impl MeshRef {
+    pub fn new(file_name: &str) -> Self {
+        let file_name = file_name.to_string();
+        let mesh_index = 0;
+
+        Self {
+            file_name,
+            mesh_index,
+        }
+    }
+
+    pub fn with_mesh_index(mut self, mesh_index: usize) -> Self {
+        self.mesh_index = mesh_index;
+        self
+    }
+}
+
+// a texture loaded through the asset server, resolved to a material handle
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MaterialRef {
+    pub file_name: String,
+}
+
+impl MaterialRef {
+    pub fn new(file_name: &str) -> Self {
+        let file_name = file_name.to_string();
+
+        Self {
+            file_name,
+        }
+    }
+}
@@ -1,6 +0,0 @@
-use bevy_ecs::prelude::*;
-
-#[derive(Component)]
-pub struct SpeedComponent {
-    pub speed: f32,
-}
@@ -0,0 +1,133 @@
+use bevy_ecs::{prelude::*, schedule::SystemConfigs};
+use cgmath::{Matrix4, One, Quaternion, SquareMatrix, Vector3, Vector4};
+use serde::{Deserialize, Serialize};
+
+use super::hierarchy::{sync_children, Children, Parent};
+
+// relative to the parent, if the entity has one
+#[derive(Component, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Transform {
+    pub translation: Vector3<f32>,
+    pub rotation: Quaternion<f32>,
+    pub scale: Vector3<f32>,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        let translation = Vector3::new(0.0, 0.0, 0.0);
+        let rotation = Quaternion::one();
+        let scale = Vector3::new(1.0, 1.0, 1.0);
+
+        Self {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+}
+
+impl Transform {
+    pub fn from_translation(translation: Vector3<f32>) -> Self {
+        Self {
+            translation,
+            ..Default::default()
+        }
+    }
+
+    pub fn from_xyz(x: f32, y: f32, z: f32) -> Self {
+        Self::from_translation(Vector3::new(x, y, z))
+    }
+
+    pub fn with_rotation(mut self, rotation: Quaternion<f32>) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: Vector3<f32>) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn matrix(&self) -> Matrix4<f32> {
+        Matrix4::from_translation(self.translation)
+            * Matrix4::from(self.rotation)
+            * Matrix4::from_nonuniform_scale(self.scale.x, self.scale.y, self.scale.z)
+    }
+}
+
+// the world matrix, computed from the transforms
+// of the entity and its ancestors every tick
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct GlobalTransform(pub Matrix4<f32>);
+
+impl Default for GlobalTransform {
+    fn default() -> Self {
+        Self(Matrix4::identity())
+    }
+}
+
+impl GlobalTransform {
+    pub fn matrix(&self) -> Matrix4<f32> {
+        self.0
+    }
+
+    pub fn translation(&self) -> Vector3<f32> {
+        let Vector4 { x, y, z, .. } = self.0.w;
+        Vector3::new(x, y, z)
+    }
+}
+
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransformSystems {
+    Propagate,
+}
+
+pub fn transform_systems() -> SystemConfigs {
+    (add_global_transforms, sync_children, propagate_transforms)
+        .chain()
+        .in_set(TransformSystems::Propagate)
+}
+
+pub fn add_global_transforms(mut commands: Commands,
+    query: Query<Entity, (With<Transform>, Without<GlobalTransform>)>,
+) {
+    for entity in &query {
+        commands.entity(entity)
+            .insert(GlobalTransform::default());
+    }
+}
+
+pub fn propagate_transforms(entities: Query<(Entity, Option<&Parent>), With<Transform>>,
+    transforms: Query<(&Transform, Option<&Children>)>,
+    mut global_transforms: Query<&mut GlobalTransform>,
+) {
+    // entities whose parent has no transform, or was despawned, are roots
+    let roots = entities.iter()
+        .filter(|(_, parent)| parent.is_none_or(|parent| !transforms.contains(parent.get())))
+        .map(|(entity, _)| entity);
+
+    for root in roots {
+        propagate(root, Matrix4::identity(), &transforms, &mut global_transforms);
+    }
+}
+
+// every entity has a single parent, so each one is visited at most once
+fn propagate(entity: Entity,
+    parent_matrix: Matrix4<f32>,
+    transforms: &Query<(&Transform, Option<&Children>)>,
+    global_transforms: &mut Query<&mut GlobalTransform>,
+) {
+    let Ok((transform, children)) = transforms.get(entity) else {
+        return;
+    };
+
+    let matrix = parent_matrix * transform.matrix();
+    if let Ok(mut global_transform) = global_transforms.get_mut(entity) {
+        global_transform.0 = matrix;
+    }
+
+    for child in children.into_iter().flat_map(Children::entities) {
+        propagate(*child, matrix, transforms, global_transforms);
+    }
+}
@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+use winit::keyboard::KeyCode;
+
+use crate::{error::{Error, Result}, modules::{frame_capture::RecordingSettings, frame_pacer::FramePacing}};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QuitKey {
+    Disabled,
+    #[default]
+    Escape,
+    Key(KeyCode),
+}
+
+impl QuitKey {
+    pub fn matches(&self, keycode: KeyCode) -> bool {
+        match self {
+            QuitKey::Disabled => false,
+            QuitKey::Escape => keycode == KeyCode::Escape,
+            QuitKey::Key(key) => *key == keycode,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EngineConfig {
+    pub title: String,
+    pub size: (u32, u32),
+    pub backends: wgpu::Backends,
+    pub power_preference: wgpu::PowerPreference,
+    // falls back to the first supported mode when none is given
+    // or when the surface does not support the requested one
+    pub present_mode: Option<wgpu::PresentMode>,
+    pub frame_latency: u32,
+    // fixed updates per second
+    pub tick_rate: f32,
+    pub frame_pacing: FramePacing,
+    pub quit_key: QuitKey,
+    // starts and stops recording frames
+    pub recording_key: Option<KeyCode>,
+    pub recording: RecordingSettings,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            title: "wxpg".to_string(),
+            size: (1280, 720),
+            backends: wgpu::Backends::PRIMARY,
+            power_preference: wgpu::PowerPreference::default(),
+            present_mode: None,
+            frame_latency: 2,
+            tick_rate: 20.0,
+            frame_pacing: FramePacing::default(),
+            quit_key: QuitKey::default(),
+            recording_key: None,
+            recording: RecordingSettings::default(),
+        }
+    }
+}
+
+impl EngineConfig {
+    pub fn from_file(path: &str) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|source| Error::Io { path: path.into(), source })?;
+
+        serde_json::from_str(&contents)
+            .map_err(|source| Error::Config { path: path.to_string(), source })
+    }
+
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.size = (width, height);
+        self
+    }
+
+    pub fn with_backends(mut self, backends: wgpu::Backends) -> Self {
+        self.backends = backends;
+        self
+    }
+
+    pub fn with_power_preference(mut self, power_preference: wgpu::PowerPreference) -> Self {
+        self.power_preference = power_preference;
+        self
+    }
+
+    pub fn with_present_mode(mut self, present_mode: wgpu::PresentMode) -> Self {
+        self.present_mode = Some(present_mode);
+        self
+    }
+
+    pub fn with_frame_latency(mut self, frame_latency: u32) -> Self {
+        self.frame_latency = frame_latency;
+        self
+    }
+
+    pub fn with_tick_rate(mut self, tick_rate: f32) -> Result<Self> {
+        Self::validate_tick_rate(tick_rate)?;
+        self.tick_rate = tick_rate;
+        Ok(self)
+    }
+
+    pub fn with_frame_pacing(mut self, frame_pacing: FramePacing) -> Self {
+        self.frame_pacing = frame_pacing;
+        self
+    }
+
+    pub fn with_quit_key(mut self, quit_key: QuitKey) -> Self {
+        self.quit_key = quit_key;
+        self
+    }
+
+    pub fn with_recording_key(mut self, recording_key: KeyCode) -> Self {
+        self.recording_key = Some(recording_key);
+        self
+    }
+
+    pub fn with_recording(mut self, recording: RecordingSettings) -> Self {
+        self.recording = recording;
+        self
+    }
+
+    pub fn update_dt(&self) -> f32 {
+        1.0 / self.tick_rate
+    }
+
+    // checked when the engine is built, as the fields can be set directly
+    pub fn validate(&self) -> Result<()> {
+        Self::validate_tick_rate(self.tick_rate)
+    }
+
+    // zero gives an infinite fixed delta, while negative and
+    // infinite rates never let the tick loop catch up
+    fn validate_tick_rate(tick_rate: f32) -> Result<()> {
+        if tick_rate.is_finite() && tick_rate > 0.0 {
+            Ok(())
+        } else {
+            Err(Error::InvalidTickRate(tick_rate))
+        }
+    }
+}
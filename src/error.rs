@@ -0,0 +1,153 @@
+use std::{fmt::Display, path::PathBuf};
+
+use crate::render::pipeline_system::UniformId;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Image {
+        file_name: String,
+        source: image::ImageError,
+    },
+    Obj {
+        file_name: String,
+        source: tobj::LoadError,
+    },
+    Config {
+        path: String,
+        source: serde_json::Error,
+    },
+    InvalidTickRate(f32),
+    UnsupportedAsset(String),
+    WrongAssetType(String),
+    UnknownAction(String),
+    MissingUniform(UniformId),
+    MissingUniformBuffer(UniformId),
+    MissingUniformBindGroup(UniformId),
+    Window(winit::error::OsError),
+    CreateSurface(wgpu::CreateSurfaceError),
+    NoAdapter,
+    RequestDevice(wgpu::RequestDeviceError),
+    Surface(wgpu::SurfaceError),
+    TextPrepare(glyphon::PrepareError),
+    TextRender(glyphon::RenderError),
+    CaptureUnsupported,
+    CaptureFormat(wgpu::TextureFormat),
+    BufferMap(wgpu::BufferAsyncError),
+    SaveImage {
+        path: PathBuf,
+        source: image::ImageError,
+    },
+    Scene(serde_json::Error),
+    SceneComponent {
+        name: String,
+        source: serde_json::Error,
+    },
+    SceneParent(usize),
+    PrefabEntity {
+        file_name: String,
+        idx: usize,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "Could not read {}: {}", path.display(), source),
+            Error::Image { file_name, source } => write!(f, "Could not decode image {}: {}", file_name, source),
+            Error::Obj { file_name, source } => write!(f, "Could not load OBJ file {}: {}", file_name, source),
+            Error::Config { path, source } => write!(f, "Could not parse config file {}: {}", path, source),
+            Error::InvalidTickRate(tick_rate) => write!(f, "Tick rate must be finite and positive, got {}", tick_rate),
+            Error::UnsupportedAsset(file_name) => write!(f, "No loader is available for asset {}", file_name),
+            Error::WrongAssetType(file_name) => write!(f, "Asset {} was requested with the wrong type", file_name),
+            Error::UnknownAction(action_name) => write!(f, "Tried getting state for an unknown action {}", action_name),
+            Error::MissingUniform(idx) => write!(f, "Could not find uniform with index {}", idx),
+            Error::MissingUniformBuffer(idx) => write!(f, "Uniform with index {} does not have a buffer set", idx),
+            Error::MissingUniformBindGroup(idx) => write!(f, "Uniform with index {} does not have a bind group set", idx),
+            Error::Window(source) => write!(f, "Could not create window: {}", source),
+            Error::CreateSurface(source) => write!(f, "Could not create surface: {}", source),
+            Error::NoAdapter => write!(f, "Could not find a suitable graphics adapter"),
+            Error::RequestDevice(source) => write!(f, "Could not request device: {}", source),
+            Error::Surface(source) => write!(f, "Could not acquire surface texture: {}", source),
+            Error::TextPrepare(source) => write!(f, "Could not prepare text: {}", source),
+            Error::TextRender(source) => write!(f, "Could not render text: {}", source),
+            Error::CaptureUnsupported => write!(f, "The surface does not support copying frames"),
+            Error::CaptureFormat(format) => write!(f, "Cannot capture frames with format {:?}", format),
+            Error::BufferMap(source) => write!(f, "Could not map buffer: {}", source),
+            Error::SaveImage { path, source } => write!(f, "Could not save image {}: {}", path.display(), source),
+            Error::Scene(source) => write!(f, "Invalid scene: {}", source),
+            Error::SceneComponent { name, source } => write!(f, "Invalid scene component {}: {}", name, source),
+            Error::SceneParent(idx) => write!(f, "Scene entity refers to missing parent {}", idx),
+            Error::PrefabEntity { file_name, idx } => write!(f, "Prefab {} has no entity {}", file_name, idx),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Image { source, .. } => Some(source),
+            Error::Obj { source, .. } => Some(source),
+            Error::Config { source, .. } => Some(source),
+            Error::Window(source) => Some(source),
+            Error::CreateSurface(source) => Some(source),
+            Error::RequestDevice(source) => Some(source),
+            Error::Surface(source) => Some(source),
+            Error::TextPrepare(source) => Some(source),
+            Error::TextRender(source) => Some(source),
+            Error::BufferMap(source) => Some(source),
+            Error::SaveImage { source, .. } => Some(source),
+            Error::Scene(source) => Some(source),
+            Error::SceneComponent { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<winit::error::OsError> for Error {
+    fn from(value: winit::error::OsError) -> Self {
+        Self::Window(value)
+    }
+}
+
+impl From<wgpu::CreateSurfaceError> for Error {
+    fn from(value: wgpu::CreateSurfaceError) -> Self {
+        Self::CreateSurface(value)
+    }
+}
+
+impl From<wgpu::RequestDeviceError> for Error {
+    fn from(value: wgpu::RequestDeviceError) -> Self {
+        Self::RequestDevice(value)
+    }
+}
+
+impl From<wgpu::SurfaceError> for Error {
+    fn from(value: wgpu::SurfaceError) -> Self {
+        Self::Surface(value)
+    }
+}
+
+impl From<glyphon::PrepareError> for Error {
+    fn from(value: glyphon::PrepareError) -> Self {
+        Self::TextPrepare(value)
+    }
+}
+
+impl From<glyphon::RenderError> for Error {
+    fn from(value: glyphon::RenderError) -> Self {
+        Self::TextRender(value)
+    }
+}
+
+impl From<wgpu::BufferAsyncError> for Error {
+    fn from(value: wgpu::BufferAsyncError) -> Self {
+        Self::BufferMap(value)
+    }
+}
@@ -7,8 +7,13 @@ pub mod pass_ext;
 pub mod device_ext;
 pub mod app;
 pub mod widgets;
+pub mod engine_config;
+pub mod primitives;
+pub mod error;
 
 use app::App;
+pub use error::{Error, Result};
+use engine_config::EngineConfig;
 pub use bevy_ecs;
 pub use egui;
 pub use egui_wgpu;
@@ -18,43 +23,71 @@ pub use wgpu;
 use winit::dpi::PhysicalSize;
 
 use std::sync::Arc;
-use std::time::Instant;
 
-use bevy_ecs::world::World;
+use bevy_ecs::{query::With, schedule::{ExecutorKind, Schedule}, world::World};
+use components::camera::{ActiveCamera, CameraComponent};
+use components::kinematics::kinematics_systems;
+use components::transform::transform_systems;
 use modules::asset_server::AssetServer;
 use modules::egui_renderer::EguiRenderer;
 use modules::glyphon_renderer::GlyphonRenderer;
 use modules::render_storage::RenderStorage;
 use modules::screen_server::ScreenServer;
+use modules::time::Time;
+use modules::frame_pacer::FramePacer;
+use modules::frame_capture::{FrameCapture, RecordingSettings};
+use modules::transition_renderer::TransitionRenderer;
+use modules::scene_renderer::SceneRenderer;
+use modules::timer_server::TimerServer;
+use modules::event_bus::{AssetLoaded, EventBus, FocusChanged, WindowResized};
+use modules::scene_server::SceneServer;
 use render::texture::*;
+use render::gpu_capabilities::GpuCapabilities;
 use render::instance_data::*;
 
 use modules::default_pipeline::DefaultPipeline;
 use modules::frame_context::FrameContext;
-use wgpu::Features;
+use log::{error, warn};
 use winit::application::ApplicationHandler;
 use winit::event_loop::ActiveEventLoop;
 use winit::event_loop::ControlFlow;
 use winit::window::WindowAttributes;
 use winit::window::WindowId;
 use winit::{
-    event::*, event_loop::EventLoop, keyboard::{KeyCode, PhysicalKey}, window::Window
+    event::*, event_loop::EventLoop, keyboard::PhysicalKey, window::Window
 };
 
 pub struct EngineInternal {
     pub asset_server: AssetServer,
     pub input_server: InputServer,
+    pub time: Time,
+    pub frame_pacer: FramePacer,
+    pub frame_capture: FrameCapture,
+    pub timer_server: TimerServer,
+    pub event_bus: EventBus,
+    pub scene_server: SceneServer,
 
     pub glyphon_renderer: GlyphonRenderer,
     pub egui_renderer: EguiRenderer,
+    pub transition_renderer: TransitionRenderer,
+    pub scene_renderer: SceneRenderer,
 
     pub world: World,
+    // run against the world every tick, after the screens have updated
+    pub update_schedule: Schedule,
+    // run against the world every frame, before the scene is prepared
+    pub frame_schedule: Schedule,
+
+    // both are none when running headless
+    pub window: Option<Arc<Window>>,
+    pub surface: Option<wgpu::Surface<'static>>,
+    // the texture screens are rendered to when running headless
+    pub offscreen_texture: Option<Arc<Texture>>,
 
-    pub window: Arc<Window>,
     pub depth_texture: Arc<Texture>,
     pub device: wgpu::Device,
-    pub surface: wgpu::Surface<'static>,
     pub config: wgpu::SurfaceConfiguration,
+    pub capabilities: GpuCapabilities,
     pub queue: wgpu::Queue,
     pub window_size: PhysicalSize<u32>,
     pub render_storage: RenderStorage,
@@ -62,32 +95,32 @@ pub struct EngineInternal {
 }
 
 impl EngineInternal {
-    pub async fn new(event_loop: &ActiveEventLoop) -> Self {
-        let window = event_loop.create_window(WindowAttributes::default())
-            .unwrap();
+    pub async fn new(event_loop: &ActiveEventLoop, engine_config: &EngineConfig) -> Result<Self> {
+        engine_config.validate()?;
+
+        let (width, height) = engine_config.size;
+        let window_attributes = WindowAttributes::default()
+            .with_title(engine_config.title.as_str())
+            .with_inner_size(PhysicalSize::new(width, height));
+
+        let window = event_loop.create_window(window_attributes)?;
         let window = Arc::new(window);
         let window_size = window.inner_size();
 
         let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
-            backends: wgpu::Backends::PRIMARY,
+            backends: engine_config.backends,
             ..Default::default()
         });
 
-        let surface = instance.create_surface(window.clone())
-            .unwrap();
+        let surface = instance.create_surface(window.clone())?;
 
         let adapter = instance.request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::default(),
+            power_preference: engine_config.power_preference,
             compatible_surface: Some(&surface),
             force_fallback_adapter: false,
-        }).await.unwrap();
+        }).await.ok_or(Error::NoAdapter)?;
 
-        let (device, queue) = adapter.request_device(&wgpu::DeviceDescriptor {
-            required_features: Features::POLYGON_MODE_LINE | Features::MULTI_DRAW_INDIRECT | Features::INDIRECT_FIRST_INSTANCE,
-            required_limits: wgpu::Limits::default(),
-            memory_hints: wgpu::MemoryHints::Performance,
-            label: None,
-        }, None).await.unwrap();
+        let (device, queue, capabilities) = Self::request_device(&adapter).await?;
 
         let surface_caps = surface.get_capabilities(&adapter);
         let surface_format = surface_caps.formats.iter()
@@ -95,119 +128,379 @@ impl EngineInternal {
             .copied()
             .unwrap_or(surface_caps.formats[0]);
 
+        let present_mode = engine_config.present_mode
+            .filter(|mode| {
+                let supported = surface_caps.present_modes.contains(mode);
+                if !supported {
+                    warn!("Present mode {:?} is not supported, using the default one.", mode);
+                }
+
+                supported
+            })
+            .unwrap_or(surface_caps.present_modes[0]);
+
+        // frames can only be captured if the surface can be copied from
+        let mut usage = wgpu::TextureUsages::RENDER_ATTACHMENT;
+        if surface_caps.usages.contains(wgpu::TextureUsages::COPY_SRC) {
+            usage |= wgpu::TextureUsages::COPY_SRC;
+        }
+
         let config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            usage,
             format: surface_format,
             width: window_size.width,
             height: window_size.height,
-            present_mode: surface_caps.present_modes[0],
+            present_mode,
             alpha_mode: surface_caps.alpha_modes[0],
             view_formats: vec![],
-            desired_maximum_frame_latency: 2,
+            desired_maximum_frame_latency: engine_config.frame_latency,
         };
 
         surface.configure(&device, &config);
 
+        Ok(Self::from_parts(device,
+            queue,
+            config,
+            capabilities,
+            engine_config,
+            Some(window),
+            Some(surface)
+        ))
+    }
+
+    pub async fn headless(engine_config: &EngineConfig) -> Result<Self> {
+        engine_config.validate()?;
+
+        let (width, height) = engine_config.size;
+
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: engine_config.backends,
+            ..Default::default()
+        });
+
+        let mut adapter_options = wgpu::RequestAdapterOptions {
+            power_preference: engine_config.power_preference,
+            compatible_surface: None,
+            force_fallback_adapter: false,
+        };
+
+        // ci machines often only have a software adapter
+        let adapter = match instance.request_adapter(&adapter_options).await {
+            Some(adapter) => adapter,
+            None => {
+                adapter_options.force_fallback_adapter = true;
+                instance.request_adapter(&adapter_options)
+                    .await
+                    .ok_or(Error::NoAdapter)?
+            }
+        };
+
+        let (device, queue, capabilities) = Self::request_device(&adapter).await?;
+
+        // surfaces are never created, but the configuration
+        // still describes the offscreen render target
+        let config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            format: Texture::TEXTURE_FORMAT,
+            width,
+            height,
+            present_mode: wgpu::PresentMode::AutoNoVsync,
+            alpha_mode: wgpu::CompositeAlphaMode::Auto,
+            view_formats: vec![],
+            desired_maximum_frame_latency: engine_config.frame_latency,
+        };
+
+        Ok(Self::from_parts(device, queue, config, capabilities, engine_config, None, None))
+    }
+
+    async fn request_device(adapter: &wgpu::Adapter)
+        -> Result<(wgpu::Device, wgpu::Queue, GpuCapabilities)>
+    {
+        let required_features = GpuCapabilities::supported_features(adapter);
+
+        // webgl and some mobile adapters cannot satisfy the default limits
+        let required_limits = if wgpu::Limits::default().check_limits(&adapter.limits()) {
+            wgpu::Limits::default()
+        } else {
+            wgpu::Limits::downlevel_webgl2_defaults()
+                .using_resolution(adapter.limits())
+        };
+
+        let (device, queue) = adapter.request_device(&wgpu::DeviceDescriptor {
+            required_features,
+            required_limits,
+            memory_hints: wgpu::MemoryHints::Performance,
+            label: None,
+        }, None).await?;
+
+        let capabilities = GpuCapabilities::new(adapter, &device);
+
+        Ok((device, queue, capabilities))
+    }
+
+    fn from_parts(device: wgpu::Device,
+        queue: wgpu::Queue,
+        config: wgpu::SurfaceConfiguration,
+        capabilities: GpuCapabilities,
+        engine_config: &EngineConfig,
+        window: Option<Arc<Window>>,
+        surface: Option<wgpu::Surface<'static>>,
+    ) -> Self {
+        let window_size = PhysicalSize::new(config.width, config.height);
+
+        let offscreen_texture = match surface {
+            Some(_) => None,
+            None => Some(Texture::render_target(&device, &config)),
+        };
+
         let depth_texture = Texture::depth_texture(&device, &config);
 
-        let glyphon_renderer = GlyphonRenderer::new(&device, &queue);
-        let egui_renderer = EguiRenderer::new(&device, &window);
+        let glyphon_renderer = GlyphonRenderer::new(&device, &queue, config.format);
+        let egui_renderer = EguiRenderer::new(&device, window.as_deref(), config.format);
+        let transition_renderer = TransitionRenderer::new(&device, config.format);
+        let scene_renderer = SceneRenderer::default();
         let default_pipeline = DefaultPipeline::new(&device, &config);
         let render_storage = RenderStorage::default();
 
         let world = World::default();
 
+        let mut update_schedule = Schedule::default();
+        update_schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        update_schedule.add_systems((
+            kinematics_systems(),
+            transform_systems(),
+        ));
+
+        // entities spawned or moved while paused, or between
+        // ticks, are drawn with up to date global transforms
+        let mut frame_schedule = Schedule::default();
+        frame_schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        frame_schedule.add_systems(transform_systems());
+
         let asset_server = AssetServer::default();
         let input_server = InputServer::default();
+        let time = Time::new(engine_config.update_dt());
+        let frame_pacer = FramePacer::new(engine_config.frame_pacing);
+        let frame_capture = FrameCapture::default();
+        let timer_server = TimerServer::default();
+        let event_bus = EventBus::default();
+        let scene_server = SceneServer::default();
 
         Self {
             window,
             queue,
             device,
             config,
+            capabilities,
             surface,
+            offscreen_texture,
             window_size,
             egui_renderer,
+            transition_renderer,
+            scene_renderer,
             depth_texture,
             render_storage,
             glyphon_renderer,
             default_pipeline,
             asset_server,
             input_server,
+            time,
+            frame_pacer,
+            frame_capture,
+            timer_server,
+            event_bus,
+            scene_server,
             world,
+            update_schedule,
+            frame_schedule,
+        }
+    }
+
+    // the engine services are lent to the world as resources while the
+    // systems run, and are taken back afterwards
+    pub fn run_schedule(&mut self, schedule: &mut Schedule) {
+        self.world.insert_resource(std::mem::take(&mut self.input_server));
+        self.world.insert_resource(std::mem::take(&mut self.asset_server));
+        self.world.insert_resource(self.time.clone());
+
+        schedule.run(&mut self.world);
+
+        if let Some(input_server) = self.world.remove_resource() {
+            self.input_server = input_server;
+        }
+
+        if let Some(asset_server) = self.world.remove_resource() {
+            self.asset_server = asset_server;
+        }
+
+        if let Some(time) = self.world.remove_resource() {
+            self.time = time;
+        }
+    }
+
+    pub fn run_update_schedule(&mut self) {
+        let mut update_schedule = std::mem::take(&mut self.update_schedule);
+        self.run_schedule(&mut update_schedule);
+        self.update_schedule = update_schedule;
+    }
+
+    pub fn run_frame_schedule(&mut self) {
+        let mut frame_schedule = std::mem::take(&mut self.frame_schedule);
+        self.run_schedule(&mut frame_schedule);
+        self.frame_schedule = frame_schedule;
+    }
+
+    // computed every frame, so that cameras moved between ticks or while paused are
+    // drawn where they are. screens that update the pipeline by hand keep working
+    // as long as no camera entity is active
+    pub fn upload_active_camera(&mut self) {
+        let mut query = self.world.query_filtered::<&mut CameraComponent, With<ActiveCamera>>();
+        let Some(mut camera) = query.iter_mut(&mut self.world).next() else {
+            return;
+        };
+
+        camera.view_proj = camera.uniform();
+
+        if let Err(err) = self.default_pipeline.update(&self.queue, &camera.view_proj) {
+            error!("{}", err);
+        }
+    }
+
+    pub fn start_recording(&mut self, settings: RecordingSettings) {
+        self.time.set_fixed_frame_delta(Some(settings.frame_delta()));
+        self.frame_capture.start_recording(settings);
+    }
+
+    pub fn stop_recording(&mut self) {
+        self.time.set_fixed_frame_delta(None);
+        self.frame_capture.stop_recording();
+    }
+
+    pub fn toggle_recording(&mut self, settings: RecordingSettings) {
+        if self.frame_capture.is_recording() {
+            self.stop_recording();
+        } else {
+            self.start_recording(settings);
         }
     }
 }
 
-pub struct Engine {
-    delta_time: Instant,
-    time_accumulator: f32,
-    update_dt: f32,
+pub struct Engine<'a> {
+    app: &'a mut dyn App,
+    exit_requested: bool,
+    engine_config: EngineConfig,
 
     engine_internal: Option<EngineInternal>,
     screen_server: ScreenServer,
 }
 
-impl Engine {
-    fn new(app: &mut impl App) -> Self{
+impl<'a> Engine<'a> {
+    fn new(app: &'a mut impl App, engine_config: EngineConfig) -> Self {
         let engine_internal = Option::default();
         let mut screen_server = ScreenServer::default();
         app.start(&mut screen_server);
 
-        let delta_time = Instant::now();
-        let time_accumulator = f32::default();
-        let update_dt = 1.0/20.0;
+        let exit_requested = false;
 
         Self {
-            delta_time,
-            time_accumulator,
-            update_dt,
+            app,
+            exit_requested,
+            engine_config,
             engine_internal,
             screen_server,
         }
     }
+
+    // creates an engine that renders to an offscreen texture.
+    // there is no event loop, so frame or advance must be called manually
+    pub fn headless(app: &'a mut impl App, engine_config: EngineConfig) -> Result<Self> {
+        let engine_internal = pollster::block_on(EngineInternal::headless(&engine_config))?;
+        let mut engine = Self::new(app, engine_config);
+        engine.engine_internal = Some(engine_internal);
+        engine.app.on_engine_ready(engine.engine_internal.as_mut().unwrap());
+
+        Ok(engine)
+    }
+
+    // headless engines have no event loop to report exiting,
+    // so this must be called once the app is done
+    pub fn shutdown(mut self) {
+        if let Some(engine_internal) = self.engine_internal.as_mut() {
+            self.app.on_exit(engine_internal);
+        }
+    }
+
+    pub fn engine_internal(&mut self) -> &mut EngineInternal {
+        self.engine_internal.as_mut().unwrap()
+    }
+
+    pub fn screen_server(&mut self) -> &mut ScreenServer {
+        &mut self.screen_server
+    }
 }
 
-impl ApplicationHandler for Engine {
+impl ApplicationHandler for Engine<'_> {
     fn window_event(
         &mut self,
         event_loop: &ActiveEventLoop,
         _window_id: WindowId,
         event: WindowEvent
     ) {
-        let engine_internal = self.engine_internal.as_mut().unwrap();
+        let Some(engine_internal) = self.engine_internal.as_mut() else {
+            return;
+        };
+
+        if !matches!(event, WindowEvent::RedrawRequested) {
+            engine_internal.frame_pacer.request_redraw();
+        }
 
         match event {
-            WindowEvent::CloseRequested | WindowEvent::KeyboardInput {
-                event: KeyEvent {
-                    state: ElementState::Pressed,
-                    physical_key: PhysicalKey::Code(KeyCode::Escape),
-                    ..
-                },
-                ..
-            } => event_loop.exit(),
+            WindowEvent::CloseRequested => event_loop.exit(),
             WindowEvent::Resized(physical_size) => {
                 self.resize(physical_size);
+
+                let engine_internal = self.engine_internal.as_mut().unwrap();
+                engine_internal.event_bus.send(WindowResized { size: physical_size });
+                self.app.on_resize(engine_internal, physical_size);
+            },
+            WindowEvent::Focused(focused) => {
+                engine_internal.event_bus.send(FocusChanged { focused });
+                self.app.on_focus_changed(engine_internal, focused);
             },
             WindowEvent::RedrawRequested => {
-                self.redraw_requested();
+                self.frame();
+
+                if self.exit_requested {
+                    event_loop.exit();
+                }
             },
             WindowEvent::KeyboardInput {
                 event: KeyEvent {
                     state,
                     physical_key: PhysicalKey::Code(keycode),
+                    repeat,
                     ..
                 },
                 ..
             } => {
+                if state.is_pressed() && self.engine_config.quit_key.matches(keycode) {
+                    event_loop.exit();
+                }
+
+                if state.is_pressed() && !repeat && self.engine_config.recording_key == Some(keycode) {
+                    engine_internal.toggle_recording(self.engine_config.recording.clone());
+                }
+
                 engine_internal.input_server.keyboard_input(keycode, state);
             },
             _ => {}
         }
 
         let engine_internal = self.engine_internal.as_mut().unwrap();
-        engine_internal.egui_renderer
-            .window_event(&engine_internal.window, &event);
+        if let Some(window) = engine_internal.window.as_ref() {
+            engine_internal.egui_renderer
+                .window_event(window, &event);
+        }
     }
 
     fn device_event(
@@ -216,65 +509,175 @@ impl ApplicationHandler for Engine {
         _device_id: DeviceId,
         event: DeviceEvent
     ) {
+        let Some(engine_internal) = self.engine_internal.as_mut() else {
+            return;
+        };
+
         if let DeviceEvent::MouseMotion { delta } = event {
-            let engine_internal = self.engine_internal.as_mut().unwrap();
             engine_internal.input_server.set_mouse_delta(delta);
+            engine_internal.frame_pacer.request_redraw();
         }
     }
 
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
-        let engine_internal = pollster::block_on(EngineInternal::new(event_loop));
-        self.engine_internal = Some(engine_internal);
+        // some platforms resume more than once, the engine is only created the first time
+        if let Some(engine_internal) = self.engine_internal.as_mut() {
+            self.app.on_resume(engine_internal);
+            return;
+        }
+
+        match pollster::block_on(EngineInternal::new(event_loop, &self.engine_config)) {
+            Ok(engine_internal) => {
+                let engine_internal = self.engine_internal.insert(engine_internal);
+                self.app.on_engine_ready(engine_internal);
+            },
+            Err(err) => {
+                error!("Could not start engine: {}", err);
+                event_loop.exit();
+            }
+        }
     }
 
-    fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
-        let engine_internal = self.engine_internal.as_ref().unwrap();
-        engine_internal.window.request_redraw();
+    fn suspended(&mut self, _event_loop: &ActiveEventLoop) {
+        if let Some(engine_internal) = self.engine_internal.as_mut() {
+            self.app.on_suspend(engine_internal);
+        }
+    }
+
+    fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
+        if let Some(engine_internal) = self.engine_internal.as_mut() {
+            self.app.on_exit(engine_internal);
+        }
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        // the engine could have failed to start
+        let Some(engine_internal) = self.engine_internal.as_mut() else {
+            return;
+        };
+
+        if !engine_internal.frame_pacer.wait(event_loop) {
+            return;
+        }
+
+        if let Some(window) = engine_internal.window.as_ref() {
+            window.request_redraw();
+        }
     }
 }
 
-impl Engine {
-    fn redraw_requested(&mut self) {
-        self.time_accumulator += self.delta_time
-            .elapsed()
-            .as_secs_f32();
-        self.delta_time = Instant::now();
+impl Engine<'_> {
+    // runs a whole frame, advancing time by the wall-clock time since the
+    // last one. headless engines have no event loop to do this for them
+    pub fn frame(&mut self) {
+        self.engine_internal.as_mut().unwrap().time.advance_frame();
+        self.run_frame();
+    }
+
+    // same as frame, but always advances time by delta seconds
+    pub fn advance(&mut self, delta: f32) {
+        self.engine_internal.as_mut().unwrap().time.advance_frame_by(delta);
+        self.run_frame();
+    }
+
+    fn run_frame(&mut self) {
+        let engine_internal = self.engine_internal.as_mut().unwrap();
+        let delta = engine_internal.time.delta();
+        engine_internal.timer_server.advance_seconds(delta);
+        self.screen_server.fire_timers(engine_internal);
 
-        while self.time_accumulator >= self.update_dt {
+        while self.engine_internal().time.consume_tick() {
             self.update();
-            self.time_accumulator -= self.update_dt;
         }
 
         self.draw();
     }
 
-    fn update(&mut self) {
+    pub fn update(&mut self) {
         let engine_internal = self.engine_internal.as_mut().unwrap();
+        engine_internal.time.count_tick();
+        engine_internal.event_bus.swap();
+        engine_internal.timer_server.advance_ticks();
+        self.screen_server.fire_timers(engine_internal);
+
         self.screen_server.update(engine_internal);
+        engine_internal.run_update_schedule();
         engine_internal.input_server.reset_mouse_delta();
+
+        for file_name in engine_internal.asset_server.drain_loaded() {
+            engine_internal.event_bus.send(AssetLoaded { file_name });
+        }
     }
 
-    fn draw(&mut self) {
+    pub fn draw(&mut self) {
         let screen_server = &mut self.screen_server;
         let engine_internal = self.engine_internal.as_mut().unwrap();
-        screen_server.draw(engine_internal);
+
+        // minimized windows have nothing to draw to. the surface
+        // is reconfigured by the next resize to a non-zero size
+        if let Some(window) = engine_internal.window.as_ref() {
+            let size = window.inner_size();
+            if size.width == 0 || size.height == 0 {
+                return;
+            }
+        }
+
+        let frame_ctx_res = match (&engine_internal.surface, &engine_internal.offscreen_texture) {
+            (Some(surface), _) => FrameContext::new(surface),
+            (None, Some(texture)) => Ok(FrameContext::offscreen(texture.clone())),
+            (None, None) => unreachable!("Engine has no render target"),
+        };
+
+        let mut frame_ctx = match frame_ctx_res {
+            Ok(frame_ctx) => frame_ctx,
+            Err(Error::Surface(err)) => {
+                self.frame_dropped(err);
+                return;
+            },
+            Err(err) => {
+                error!("Skipping frame: {}", err);
+                return;
+            }
+        };
+
+        engine_internal.time.count_frame();
+        engine_internal.event_bus.begin_frame();
+        engine_internal.scene_server.resolve_assets(&mut engine_internal.asset_server,
+            &mut engine_internal.render_storage,
+            &engine_internal.device,
+            &engine_internal.queue,
+            &mut engine_internal.world
+        );
+        engine_internal.run_frame_schedule();
+        engine_internal.scene_renderer.prepare(&engine_internal.device,
+            &engine_internal.queue,
+            &engine_internal.render_storage,
+            &mut engine_internal.world
+        );
+        engine_internal.upload_active_camera();
+
+        screen_server.draw(engine_internal, &mut frame_ctx);
 
         let device = &engine_internal.device;
         let config = &engine_internal.config;
         let queue = &engine_internal.queue;
-        let window = &engine_internal.window;
-        let surface = &engine_internal.surface;
-
-        let mut frame_ctx = FrameContext::new(surface);
+        let window = engine_internal.window.as_deref();
 
         engine_internal.egui_renderer
-            .draw(device, queue, config, window, screen_server, &mut frame_ctx);
+            .draw(device, queue, config, window, screen_server, &mut engine_internal.event_bus, &mut frame_ctx);
+        engine_internal.event_bus.end_frame();
+
+        if let Err(err) = engine_internal.glyphon_renderer
+            .draw(device, queue, config, &mut frame_ctx)
+        {
+            error!("{}", err);
+        }
 
-        engine_internal.glyphon_renderer
-            .draw(device, queue, config, &mut frame_ctx);
+        // copied last so that the capture includes the overlays
+        let readback = engine_internal.frame_capture
+            .copy_frame(device, config, &mut frame_ctx);
 
-        let buffers = frame_ctx
-            .encoders
+        let buffers = std::mem::take(&mut frame_ctx.encoders)
             .into_iter()
             .map(|encoder| {
                 encoder.finish()
@@ -282,7 +685,43 @@ impl Engine {
             .collect::<Vec<_>>();
 
         engine_internal.queue.submit(buffers);
-        frame_ctx.output.present();
+        frame_ctx.present();
+
+        if let Some(readback) = readback {
+            if let Err(err) = readback.save(&engine_internal.device) {
+                error!("Could not capture frame: {}", err);
+            }
+        }
+    }
+
+    fn frame_dropped(&mut self, err: wgpu::SurfaceError) {
+        warn!("Dropped frame: {}", err);
+
+        match err {
+            wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated => {
+                let engine_internal = self.engine_internal.as_mut().unwrap();
+                let size = match engine_internal.window.as_ref() {
+                    Some(window) => window.inner_size(),
+                    None => engine_internal.window_size,
+                };
+
+                self.resize(size);
+            },
+            wgpu::SurfaceError::OutOfMemory => {
+                error!("Surface is out of memory, exiting.");
+                self.exit_requested = true;
+            },
+            wgpu::SurfaceError::Timeout => {},
+        }
+
+        let engine_internal = self.engine_internal.as_mut().unwrap();
+        self.screen_server.frame_dropped(engine_internal, &err);
+    }
+
+    // set when the engine cannot keep running, such as
+    // when the surface runs out of memory
+    pub fn exit_requested(&self) -> bool {
+        self.exit_requested
     }
 
     fn resize(&mut self, new_size: PhysicalSize<u32>) {
@@ -295,17 +734,27 @@ impl Engine {
             let device = &engine_internal.device;
             let config = &engine_internal.config;
             engine_internal.depth_texture = Texture::depth_texture(device, config);
-            engine_internal.surface.configure(device, config);
+
+            let aspect = new_size.width as f32 / new_size.height as f32;
+            let mut cameras = engine_internal.world.query::<&mut CameraComponent>();
+            for mut camera in cameras.iter_mut(&mut engine_internal.world) {
+                camera.aspect = aspect;
+            }
+
+            match engine_internal.surface.as_ref() {
+                Some(surface) => surface.configure(device, config),
+                None => engine_internal.offscreen_texture = Some(Texture::render_target(device, config)),
+            }
         }
     }
 }
 
-pub fn run(app: &mut impl App) {
+pub fn run(app: &mut impl App, engine_config: EngineConfig) {
     env_logger::init();
 
     let event_loop = EventLoop::new().unwrap();
     event_loop.set_control_flow(ControlFlow::Poll);
 
-    let mut engine = Engine::new(app);
+    let mut engine = Engine::new(app, engine_config);
     let _ = event_loop.run_app(&mut engine);
 }
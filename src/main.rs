@@ -1,7 +1,15 @@
 use egui::Align2;
 
+use cgmath::{One, Quaternion};
 use winit::keyboard::KeyCode;
-use wxpg::{app::App, modules::{commands::Commands, default_pipeline::{self, DefaultPipeline}, egui_renderer::{EguiWidget, EguiWindow}, input_server, render_storage::RenderStorage, screen_server::{GameState, ScreenServer}}, primitives::cube::Cube, render::{camera::FpsCamera, pipeline_system::Pipeline, texture::Texture}, run, screens::screen::Screen, widgets::fps_visualizer::FpsGraph};
+use wxpg::{app::App, components::{renderable::MeshHandle, transform::Transform}, engine_config::EngineConfig, modules::{commands::Commands, egui_renderer::{EguiWidget, EguiWindow}, event_bus::EventBus, render_storage::RenderStorage, screen_server::ScreenServer, states::States}, primitives::cube::Cube, render::{camera::FpsCamera, instance_data::InstanceData, texture::Texture}, run, screens::screen::Screen, widgets::fps_visualizer::FpsGraph};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum GameState {
+    Menu,
+}
+
+impl States for GameState {}
 
 #[derive(Default)]
 pub struct TestWindow {
@@ -10,7 +18,7 @@ pub struct TestWindow {
 }
 
 impl EguiWindow for TestWindow {
-    fn ui(&mut self, ctx: &egui::Context) {
+    fn ui(&mut self, ctx: &egui::Context, _events: &mut EventBus) {
         self.counter += 1;
         if self.counter > 100 {
             self.counter = 0;
@@ -33,23 +41,36 @@ impl EguiWindow for TestWindow {
 pub struct TestScreen {
     render_storage: RenderStorage,
     camera: Option<FpsCamera>,
-    default_pipeline: Option<DefaultPipeline>,
 }
 
 impl Screen for TestScreen {
-    fn start(&mut self, commands: &mut Commands) {
+    fn init(&mut self, commands: &mut Commands) {
         let device = &commands.engine_internal.device;
         let config = &commands.engine_internal.config;
         let queue = &commands.engine_internal.queue;
         let input_server = &mut commands.engine_internal.input_server;
         let asset_server = &mut commands.engine_internal.asset_server;
 
-        let texture = Texture::debug(asset_server, device, queue);
-        self.render_storage.push_material(texture, device);
+        let texture = Texture::debug(asset_server, device, queue)
+            .expect("The debug texture should always be available");
+        self.render_storage.push_material(texture.clone(), device);
 
-        let cube = Cube::default();
+        let mut cube = Cube::default();
+        cube.add_instance(InstanceData {
+            position: (0.0, 0.0, 5.0).into(),
+            rotation: Quaternion::one(),
+        });
         self.render_storage.push_mesh(&cube, device);
 
+        let render_storage = &mut commands.engine_internal.render_storage;
+        render_storage.push_material(texture, device);
+        let mesh_id = render_storage.push_mesh(&Cube::default(), device);
+
+        commands.engine_internal.world.spawn((
+            Transform::from_xyz(2.0, 0.0, 5.0),
+            MeshHandle(mesh_id),
+        ));
+
         input_server.register_action("camera_up", KeyCode::Space);
         input_server.register_action("camera_right", KeyCode::ShiftLeft);
         input_server.register_action("camera_down", KeyCode::ArrowDown);
@@ -57,36 +78,30 @@ impl Screen for TestScreen {
         input_server.register_action("camera_front", KeyCode::ArrowUp);
         input_server.register_action("camera_back", KeyCode::ArrowDown);
 
-        let default_pipeline = DefaultPipeline::new(device, config);
         let camera = FpsCamera::new(config.width as f32,
             config.height as f32,
-            1.0
+            20.0
         );
 
         self.camera = Some(camera);
-        self.default_pipeline = Some(default_pipeline);
+        commands.register_egui_window(TestWindow::default(), GameState::Menu);
     }
 
     fn update(&mut self, commands: &mut Commands) {
         let queue = &commands.engine_internal.queue;
         let input_server = &commands.engine_internal.input_server;
+        let pipeline = &mut commands.engine_internal.default_pipeline;
+        let delta = commands.engine_internal.time.fixed_delta();
         let camera = self.camera.as_mut().unwrap();
-        let pipeline = self.default_pipeline.as_mut().unwrap();
 
-        camera.update(input_server);
-        pipeline.update(queue, &camera.transform().uniform());
+        camera.update(input_server, delta);
+        if let Err(err) = pipeline.update(queue, &camera.transform().uniform()) {
+            log::error!("{}", err);
+        }
     }
 
-    // TODO can this process be automated?
-    fn draw(&mut self, commands: &mut Commands) {
-        let device = &commands.engine_internal.device;
-        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("Object Encoder"),
-        });
-        let view = &commands.engine_internal.
-
-        let pass = self.default_pipeline.unwrap()
-            .pass(encoder, view, depth_texture_view)
+    fn render_storage(&self) -> Option<&RenderStorage> {
+        Some(&self.render_storage)
     }
 }
 
@@ -101,5 +116,5 @@ impl App for AppTest {
 
 fn main() {
     let mut app = AppTest::default();
-    run(&mut app);
+    run(&mut app, EngineConfig::default().with_title("wxpg test"));
 }
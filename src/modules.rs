@@ -3,7 +3,18 @@ pub mod default_pipeline;
 pub mod frame_context;
 pub mod asset_server;
 pub mod screen_server;
+pub mod states;
 pub mod render_storage;
 pub mod egui_renderer;
 pub mod glyphon_renderer;
 pub mod commands;
+pub mod time;
+pub mod frame_pacer;
+pub mod frame_capture;
+pub mod screen_transition;
+pub mod transition_renderer;
+pub mod scene_renderer;
+pub mod timer_server;
+pub mod event_bus;
+pub mod scene_server;
+pub mod prefab;
@@ -1,15 +1,21 @@
 use std::{any::{Any, TypeId}, collections::HashMap, hash::{DefaultHasher, Hash, Hasher}, sync::Arc};
 
+use bevy_ecs::system::Resource;
+use log::error;
 
-use crate::{render::model::Model, util::get_extension, Texture};
+use crate::{error::{Error, Result}, render::model::Model, util::get_extension, Texture};
+
+use super::prefab::Prefab;
 
 pub trait Asset {
     fn file_name(&self) -> &str;
 }
 
-#[derive(Default)]
+#[derive(Default, Resource)]
 pub struct AssetServer {
     map: HashMap<(TypeId, u64), Arc<dyn Any + Send + Sync>>,
+    // file names loaded since the engine last published them
+    loaded: Vec<String>,
 }
 
 impl AssetServer {
@@ -54,7 +60,7 @@ impl AssetServer {
         file_name: &str,
         device: &wgpu::Device,
         queue: &wgpu::Queue
-    ) -> Option<Arc<T>> where T: Asset + Send + Sync + 'static {
+    ) -> Result<Arc<T>> where T: Asset + Send + Sync + 'static {
         let type_id = TypeId::of::<T>();
         let hash = {
             let mut hasher = DefaultHasher::new();
@@ -62,32 +68,64 @@ impl AssetServer {
             hasher.finish()
         };
 
-        match self.map.get(&(type_id, hash)) {
-            Some(any) => any.clone().downcast().ok(),
-            None => {
-                self.load(file_name, device, queue);
-                self.get(file_name)
-            }
+        if !self.map.contains_key(&(type_id, hash)) {
+            self.load(file_name, device, queue)?;
         }
+
+        self.get(file_name)
+            .ok_or_else(|| Error::WrongAssetType(file_name.to_string()))
+            .inspect_err(|err| error!("{}", err))
     }
 
-    fn load(&mut self, file_name: &str, device: &wgpu::Device, queue: &wgpu::Queue) {
+    fn load(&mut self,
+        file_name: &str,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue
+    ) -> Result<()> {
         let extension = get_extension(file_name);
 
         match extension {
             Some("png") | Some("jpg") => self.load_texture(file_name, device, queue),
             Some("obj") => self.load_model(file_name, device, queue),
-            _ => {}
-        }
+            Some("prefab") => self.load_prefab(file_name),
+            _ => {
+                let err = Error::UnsupportedAsset(file_name.to_string());
+                error!("{}", err);
+                Err(err)
+            }
+        }?;
+
+        self.loaded.push(file_name.to_string());
+        Ok(())
     }
 
-    fn load_texture(&mut self, file_name: &str, device: &wgpu::Device, queue: &wgpu::Queue) {
-        let texture = Texture::load(file_name, device, queue);
+    pub(crate) fn drain_loaded(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.loaded)
+    }
+
+    fn load_texture(&mut self,
+        file_name: &str,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue
+    ) -> Result<()> {
+        let texture = Texture::load(file_name, device, queue)?;
         self.insert(texture);
+        Ok(())
     }
 
-    fn load_model(&mut self, file_name: &str, device: &wgpu::Device, queue: &wgpu::Queue) {
-        let model = Model::load(file_name, self, device, queue);
+    fn load_model(&mut self,
+        file_name: &str,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue
+    ) -> Result<()> {
+        let model = Model::load(file_name, self, device, queue)?;
         self.insert(model);
+        Ok(())
+    }
+
+    fn load_prefab(&mut self, file_name: &str) -> Result<()> {
+        let prefab = Prefab::load(file_name)?;
+        self.insert(prefab);
+        Ok(())
     }
 }
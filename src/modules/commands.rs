@@ -1,35 +1,227 @@
-use crate::EngineInternal;
+use std::path::{Path, PathBuf};
 
-use super::{egui_renderer::EguiWindow, screen_server::GameState};
+use bevy_ecs::entity::Entity;
+
+use crate::{components::transform::Transform, error::{Error, Result}, EngineInternal};
+
+use super::{egui_renderer::EguiWindow, frame_capture::RecordingSettings, frame_context::FrameContext, frame_pacer::FramePacing, screen_server::StateTransition, screen_transition::ScreenTransition, states::{StateKey, States}, event_bus::TimerFired, prefab::{Prefab, PrefabOverrides}, time::Time, timer_server::{TimerAction, TimerDuration, TimerId}};
 
 pub struct Commands<'a> {
-    pub new_state: Option<GameState>,
+    // every requested transition is applied, in order
+    pub transitions: Vec<(StateTransition, Option<ScreenTransition>)>,
     pub engine_internal: &'a mut EngineInternal,
+    // only available while drawing
+    pub frame_ctx: Option<&'a mut FrameContext>,
+    // the state of the screen being called, which owns its timers
+    pub state: Option<StateKey>,
 }
 
 impl<'a> Commands<'a> {
     pub fn new(engine_internal: &'a mut EngineInternal) -> Self {
-        let new_state = Option::default();
+        let transitions = Vec::new();
+        let frame_ctx = None;
+        let state = None;
         
         Self {
-            new_state,
+            transitions,
             engine_internal,
+            frame_ctx,
+            state,
         }
     }
+
+    pub fn with_frame(engine_internal: &'a mut EngineInternal,
+        frame_ctx: &'a mut FrameContext,
+    ) -> Self {
+        let mut commands = Self::new(engine_internal);
+        commands.frame_ctx = Some(frame_ctx);
+        commands
+    }
+
+    pub fn frame_ctx(&mut self) -> Option<&mut FrameContext> {
+        self.frame_ctx.as_deref_mut()
+    }
+
+    pub fn depth_view(&self) -> &wgpu::TextureView {
+        self.engine_internal.depth_texture.view()
+    }
     
-    pub fn set_state(&mut self, state: GameState) {
-        self.new_state = Some(state);
+    pub fn set_state<S: States>(&mut self, state: S) {
+        self.transitions.push((StateTransition::Set(StateKey::new(state)), None));
+    }
+
+    pub fn push_state<S: States>(&mut self, state: S) {
+        self.transitions.push((StateTransition::Push(StateKey::new(state)), None));
+    }
+
+    pub fn pop_state(&mut self) {
+        self.transitions.push((StateTransition::Pop, None));
+    }
+
+    pub fn replace_state<S: States>(&mut self, state: S) {
+        self.transitions.push((StateTransition::Replace(StateKey::new(state)), None));
+    }
+
+    pub fn set_state_with<S: States>(&mut self, state: S, transition: ScreenTransition) {
+        self.transitions.push((StateTransition::Set(StateKey::new(state)), Some(transition)));
+    }
+
+    pub fn push_state_with<S: States>(&mut self, state: S, transition: ScreenTransition) {
+        self.transitions.push((StateTransition::Push(StateKey::new(state)), Some(transition)));
     }
 
-    pub fn register_egui_window(&mut self,
+    pub fn pop_state_with(&mut self, transition: ScreenTransition) {
+        self.transitions.push((StateTransition::Pop, Some(transition)));
+    }
+
+    pub fn replace_state_with<S: States>(&mut self, state: S, transition: ScreenTransition) {
+        self.transitions.push((StateTransition::Replace(StateKey::new(state)), Some(transition)));
+    }
+
+    pub fn register_egui_window<S: States>(&mut self,
         window: impl EguiWindow + 'static,
-        required_state: GameState
+        required_state: S
     ) {
         self.engine_internal.egui_renderer
-            .register_window(window, required_state);
+            .register_window(window, StateKey::new(required_state));
+    }
+
+    pub fn transitions(&self) -> &[(StateTransition, Option<ScreenTransition>)] {
+        &self.transitions
+    }
+
+    pub fn time(&self) -> &Time {
+        &self.engine_internal.time
+    }
+
+    pub fn time_mut(&mut self) -> &mut Time {
+        &mut self.engine_internal.time
+    }
+
+    pub fn interpolation_alpha(&self) -> f32 {
+        self.engine_internal.time.alpha()
+    }
+
+    pub fn frame_pacing(&self) -> FramePacing {
+        self.engine_internal.frame_pacer.pacing()
+    }
+
+    pub fn set_frame_pacing(&mut self, frame_pacing: FramePacing) {
+        self.engine_internal.frame_pacer.set_pacing(frame_pacing);
+    }
+
+    // saves the next presented frame, overlays included, as a png
+    pub fn request_screenshot(&mut self, path: impl Into<PathBuf>) {
+        self.engine_internal.frame_capture.request(path);
+    }
+
+    pub fn start_recording(&mut self, settings: RecordingSettings) {
+        self.engine_internal.start_recording(settings);
+    }
+
+    pub fn stop_recording(&mut self) {
+        self.engine_internal.stop_recording();
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.engine_internal.frame_capture.is_recording()
+    }
+
+    // runs the callback once, after the duration
+    pub fn after(&mut self,
+        duration: TimerDuration,
+        callback: impl FnMut(&mut Commands) + 'static,
+    ) -> TimerId {
+        self.add_timer(duration, false, TimerAction::Callback(Box::new(callback)))
+    }
+
+    // runs the callback every time the duration elapses
+    pub fn every(&mut self,
+        duration: TimerDuration,
+        callback: impl FnMut(&mut Commands) + 'static,
+    ) -> TimerId {
+        self.add_timer(duration, true, TimerAction::Callback(Box::new(callback)))
+    }
+
+    pub fn after_event(&mut self, duration: TimerDuration, event_name: &str) -> TimerId {
+        self.add_timer(duration, false, TimerAction::Event(event_name.to_string()))
+    }
+
+    pub fn every_event(&mut self, duration: TimerDuration, event_name: &str) -> TimerId {
+        self.add_timer(duration, true, TimerAction::Event(event_name.to_string()))
+    }
+
+    pub fn cancel_timer(&mut self, timer_id: TimerId) {
+        self.engine_internal.timer_server.cancel(timer_id);
+    }
+
+    // whether a timer emitted the event during the last tick
+    pub fn timer_fired(&self, event_name: &str) -> bool {
+        self.read::<TimerFired>()
+            .iter()
+            .any(|event| event.event_name == event_name)
+    }
+
+    pub fn send<T: Send + Sync + 'static>(&mut self, event: T) {
+        self.engine_internal.event_bus.send(event);
+    }
+
+    // the events sent during the last tick. while drawing, the
+    // events that became readable since the last frame instead
+    pub fn read<T: Send + Sync + 'static>(&self) -> &[T] {
+        self.engine_internal.event_bus.read()
+    }
+
+    // spawns the scene's entities into the engine world. their meshes
+    // and materials are loaded before they are first drawn
+    pub fn load_scene(&mut self, path: impl AsRef<Path>) -> Result<Vec<Entity>> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .map_err(|source| Error::Io { path: path.into(), source })?;
+
+        let engine_internal = &mut *self.engine_internal;
+        engine_internal.scene_server
+            .load(&mut engine_internal.world, &json)
+    }
+
+    pub fn save_scene(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let json = self.engine_internal.scene_server
+            .save(&self.engine_internal.world)?;
+
+        std::fs::write(path, json)
+            .map_err(|source| Error::Io { path: path.into(), source })
+    }
+
+    // loads the prefab through the asset server the first
+    // time, and returns the root of the spawned entity tree
+    pub fn spawn_prefab(&mut self, file_name: &str, transform: Transform) -> Result<Entity> {
+        self.spawn_prefab_with(file_name, transform, &PrefabOverrides::default())
+    }
+
+    pub fn spawn_prefab_with(&mut self,
+        file_name: &str,
+        transform: Transform,
+        overrides: &PrefabOverrides,
+    ) -> Result<Entity> {
+        let engine_internal = &mut *self.engine_internal;
+        let prefab = engine_internal.asset_server
+            .get_or_load::<Prefab>(file_name, &engine_internal.device, &engine_internal.queue)?;
+
+        prefab.instantiate(&mut engine_internal.world,
+            engine_internal.scene_server.registry(),
+            transform,
+            overrides
+        )
     }
 
-    pub fn new_state(&self) -> Option<GameState> {
-        self.new_state
+    fn add_timer(&mut self,
+        duration: TimerDuration,
+        repeating: bool,
+        action: TimerAction,
+    ) -> TimerId {
+        let owner = self.state.clone();
+        self.engine_internal.timer_server
+            .add(duration, repeating, owner, action)
     }
 }
@@ -1,7 +1,7 @@
 use cgmath::{Matrix4, SquareMatrix};
 use wgpu::util::DeviceExt;
 
-use crate::{render::{camera::CameraUniform, pipeline_system::{AsVertexBufferLayout, Pipeline, ShaderUniform}, vertex::Vertex}, InstanceRaw};
+use crate::{error::Result, render::{camera::CameraUniform, pipeline_system::{AsVertexBufferLayout, Pipeline, ShaderUniform}, vertex::Vertex}, InstanceRaw};
 
 #[derive(Debug)]
 pub struct DefaultPipeline {
@@ -32,9 +32,15 @@ impl DefaultPipeline {
     pub fn update(&mut self,
         queue: &wgpu::Queue,
         camera_uniform: &CameraUniform
-    ) {
-        queue.write_buffer(self.internal_pipeline.buffer(1),
+    ) -> Result<()> {
+        queue.write_buffer(self.internal_pipeline.buffer(1)?,
             0, bytemuck::cast_slice(camera_uniform));
+
+        Ok(())
+    }
+
+    pub fn camera_bind_group(&self) -> Result<&wgpu::BindGroup> {
+        self.internal_pipeline.bind_group(1)
     }
 
     fn create_camera_uniform(device: &wgpu::Device) -> ShaderUniform {
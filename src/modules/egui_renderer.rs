@@ -8,37 +8,44 @@ use winit::window::Window;
 
 use crate::modules::frame_context::FrameContext;
 
-use super::screen_server::{GameState, ScreenServer};
+use super::{event_bus::EventBus, screen_server::ScreenServer, states::StateKey};
 
 pub trait EguiWidget {
     fn show(&mut self, unique_name: &str, ui: &mut Ui);
 }
 
 pub trait EguiWindow {
-    fn ui(&mut self, ctx: &Context);
+    fn ui(&mut self, ctx: &Context, events: &mut EventBus);
 }
 
 pub struct EguiRenderer {
-    state: egui_winit::State,
+    context: egui::Context,
+    // headless engines have no window to gather input from
+    state: Option<egui_winit::State>,
     renderer: egui_wgpu::Renderer,
-    window_map: HashMap<GameState, Box<dyn EguiWindow>>,
+    window_map: HashMap<StateKey, Box<dyn EguiWindow>>,
 }
 
 impl EguiRenderer {
-    pub fn new(device: &wgpu::Device, window: &Window) -> Self {
+    pub fn new(device: &wgpu::Device,
+        window: Option<&Window>,
+        format: wgpu::TextureFormat,
+    ) -> Self {
         let context = egui::Context::default();
         let viewport_id = context.viewport_id();
 
-        let state = egui_winit::State::new(context,
-            viewport_id,
-            window,
-            None,
-            None,
-            None
-        );
+        let state = window.map(|window| {
+            egui_winit::State::new(context.clone(),
+                viewport_id,
+                window,
+                None,
+                None,
+                None
+            )
+        });
 
         let renderer = egui_wgpu::Renderer::new(device,
-            wgpu::TextureFormat::Bgra8UnormSrgb,
+            format,
             None,
             1,
             false
@@ -47,6 +54,7 @@ impl EguiRenderer {
         let window_map = HashMap::new();
 
         Self {
+            context,
             state,
             renderer,
             window_map,
@@ -54,24 +62,28 @@ impl EguiRenderer {
     }
 
     pub fn window_event(&mut self, window: &Window, event: &WindowEvent) {
-        let _ = self.state.on_window_event(window, event);
+        if let Some(state) = self.state.as_mut() {
+            let _ = state.on_window_event(window, event);
+        }
     }
 
     // TODO return a en EguiWindowId to let user manage visibility of window
     pub fn register_window(&mut self,
         window: impl EguiWindow + 'static,
-        required_state: GameState,
+        required_state: StateKey,
     ) {
         let func = Box::new(window);
         self.window_map.insert(required_state, func);
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn draw(&mut self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         config: &wgpu::SurfaceConfiguration,
-        window: &Window,
+        window: Option<&Window>,
         screen_server: &mut ScreenServer,
+        events: &mut EventBus,
         frame_ctx: &mut FrameContext,
     ) {
         let view = &frame_ctx.view;
@@ -79,27 +91,36 @@ impl EguiRenderer {
             label: Some("Egui Encoder"),
         });
 
-        let input = self.state.take_egui_input(window);
-        let context = self.state.egui_ctx();
+        let input = match (self.state.as_mut(), window) {
+            (Some(state), Some(window)) => state.take_egui_input(window),
+            _ => egui::RawInput {
+                screen_rect: Some(egui::Rect::from_min_size(egui::Pos2::ZERO,
+                    egui::vec2(config.width as f32, config.height as f32)
+                )),
+                ..Default::default()
+            },
+        };
+        let context = &self.context;
 
-        let game_state = screen_server.state();
+        let visible_states = screen_server.visible_states();
 
         context.begin_pass(input);
         self.window_map
             .iter_mut()
             .for_each(|(required_state, window)| {
-                if game_state != *required_state {
+                if !visible_states.contains(required_state) {
                     return;
                 } 
 
-                window.ui(context);
+                window.ui(context, events);
             });
         let output = context.end_pass();
 
-        self.state.handle_platform_output(window, output.platform_output);
+        if let (Some(state), Some(window)) = (self.state.as_mut(), window) {
+            state.handle_platform_output(window, output.platform_output);
+        }
 
-        let tris = self.state
-            .egui_ctx()
+        let tris = self.context
             .tessellate(output.shapes,
                 output.pixels_per_point
             );
@@ -113,7 +134,7 @@ impl EguiRenderer {
 
         let screen_descriptor = ScreenDescriptor {
             size_in_pixels: [config.width, config.height],
-            pixels_per_point: window.scale_factor() as f32,
+            pixels_per_point: window.map_or(1.0, |window| window.scale_factor() as f32),
         };
 
         self.renderer
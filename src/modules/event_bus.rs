@@ -0,0 +1,195 @@
+use std::{any::{Any, TypeId}, collections::HashMap, ops::Range};
+
+use winit::dpi::PhysicalSize;
+
+use super::{states::StateKey, timer_server::TimerId};
+
+#[derive(Debug, Clone, Copy)]
+pub struct WindowResized {
+    pub size: PhysicalSize<u32>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FocusChanged {
+    pub focused: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct StateChanged {
+    pub previous: Option<StateKey>,
+    pub current: Option<StateKey>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AssetLoaded {
+    pub file_name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct TimerFired {
+    pub timer_id: TimerId,
+    pub event_name: String,
+}
+
+// events sent during a tick can be read during the next one,
+// so that every reader sees them exactly once. frames read the
+// events that became readable since the last frame, as several
+// or no ticks can run between two frames
+#[derive(Debug)]
+pub struct Events<T> {
+    // every event that is still readable, oldest first
+    events: Vec<T>,
+    // the id of the first stored event
+    first_id: usize,
+    tick_ids: Range<usize>,
+    frame_ids: Range<usize>,
+    ticks_since_frame: usize,
+}
+
+impl<T> Default for Events<T> {
+    fn default() -> Self {
+        let events = Vec::new();
+
+        Self {
+            events,
+            first_id: 0,
+            tick_ids: 0..0,
+            frame_ids: 0..0,
+            ticks_since_frame: 0,
+        }
+    }
+}
+
+impl<T> Events<T> {
+    // keeps events from piling up when ticks run without
+    // frames, such as in headless engines that only update
+    const MAX_TICKS_SINCE_FRAME: usize = 64;
+
+    pub fn send(&mut self, event: T) {
+        self.events.push(event);
+    }
+
+    // the events sent during the last tick
+    pub fn read(&self) -> &[T] {
+        self.slice(&self.tick_ids)
+    }
+
+    // the events that became readable since the last frame
+    pub fn read_frame(&self) -> &[T] {
+        self.slice(&self.frame_ids)
+    }
+
+    fn slice(&self, ids: &Range<usize>) -> &[T] {
+        &self.events[ids.start - self.first_id..ids.end - self.first_id]
+    }
+
+    fn next_id(&self) -> usize {
+        self.first_id + self.events.len()
+    }
+
+    fn swap(&mut self) {
+        self.tick_ids = self.tick_ids.end..self.next_id();
+
+        self.ticks_since_frame += 1;
+        if self.ticks_since_frame > Self::MAX_TICKS_SINCE_FRAME {
+            self.frame_ids = self.tick_ids.start..self.tick_ids.start;
+        }
+
+        self.drop_read();
+    }
+
+    fn swap_frame(&mut self) {
+        self.frame_ids = self.frame_ids.end..self.tick_ids.end;
+        self.ticks_since_frame = 0;
+
+        self.drop_read();
+    }
+
+    // drops the events that neither ticks nor frames can read anymore
+    fn drop_read(&mut self) {
+        let first_id = self.tick_ids.start.min(self.frame_ids.start);
+        self.events.drain(..first_id - self.first_id);
+        self.first_id = first_id;
+    }
+}
+
+trait EventStorage: Send + Sync {
+    fn swap(&mut self);
+    fn swap_frame(&mut self);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Send + Sync + 'static> EventStorage for Events<T> {
+    fn swap(&mut self) {
+        Events::swap(self);
+    }
+
+    fn swap_frame(&mut self) {
+        Events::swap_frame(self);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct EventBus {
+    map: HashMap<TypeId, Box<dyn EventStorage>>,
+    // set while drawing, so that reads made at frame rate
+    // do not see the same events on several frames
+    drawing: bool,
+}
+
+impl EventBus {
+    pub fn send<T: Send + Sync + 'static>(&mut self, event: T) {
+        self.events_mut::<T>()
+            .send(event);
+    }
+
+    pub fn read<T: Send + Sync + 'static>(&self) -> &[T] {
+        let Some(events) = self.events::<T>() else {
+            return &[];
+        };
+
+        if self.drawing {
+            events.read_frame()
+        } else {
+            events.read()
+        }
+    }
+
+    pub fn events<T: Send + Sync + 'static>(&self) -> Option<&Events<T>> {
+        self.map.get(&TypeId::of::<T>())
+            .and_then(|events| events.as_any().downcast_ref())
+    }
+
+    pub fn events_mut<T: Send + Sync + 'static>(&mut self) -> &mut Events<T> {
+        self.map.entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(Events::<T>::default()))
+            .as_any_mut()
+            .downcast_mut()
+            .expect("Events are always stored under their own type id")
+    }
+
+    pub(crate) fn swap(&mut self) {
+        self.map.values_mut()
+            .for_each(|events| events.swap());
+    }
+
+    pub(crate) fn begin_frame(&mut self) {
+        self.map.values_mut()
+            .for_each(|events| events.swap_frame());
+
+        self.drawing = true;
+    }
+
+    pub(crate) fn end_frame(&mut self) {
+        self.drawing = false;
+    }
+}
@@ -0,0 +1,283 @@
+use std::{path::PathBuf, sync::mpsc};
+
+use log::error;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
+use super::frame_context::FrameContext;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecordingSettings {
+    pub directory: PathBuf,
+    // only every nth presented frame is saved
+    pub frame_step: u32,
+    // the simulated frame rate, frames always advance time by
+    // the same amount no matter how long they take to save
+    pub fps: f32,
+}
+
+impl Default for RecordingSettings {
+    fn default() -> Self {
+        Self {
+            directory: PathBuf::from("recordings"),
+            frame_step: 1,
+            fps: 60.0,
+        }
+    }
+}
+
+impl RecordingSettings {
+    pub fn frame_delta(&self) -> f32 {
+        1.0 / self.fps
+    }
+}
+
+#[derive(Debug)]
+struct Recording {
+    settings: RecordingSettings,
+    frame_count: u64,
+    saved_count: u64,
+}
+
+impl Recording {
+    fn next_path(&mut self) -> Option<PathBuf> {
+        let should_save = self.frame_count.is_multiple_of(self.settings.frame_step.max(1) as u64);
+        self.frame_count += 1;
+
+        if !should_save {
+            return None;
+        }
+
+        self.saved_count += 1;
+        let file_name = format!("frame_{:05}.png", self.saved_count);
+        Some(self.settings.directory.join(file_name))
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct FrameCapture {
+    requests: Vec<PathBuf>,
+    recording: Option<Recording>,
+}
+
+impl FrameCapture {
+    pub fn request(&mut self, path: impl Into<PathBuf>) {
+        self.requests.push(path.into());
+    }
+
+    pub fn has_requests(&self) -> bool {
+        !self.requests.is_empty()
+    }
+
+    // restarts the numbering if a recording was already running
+    pub fn start_recording(&mut self, settings: RecordingSettings) {
+        self.recording = Some(Recording {
+            settings,
+            frame_count: 0,
+            saved_count: 0,
+        });
+    }
+
+    pub fn stop_recording(&mut self) {
+        self.recording = None;
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub fn recording_settings(&self) -> Option<&RecordingSettings> {
+        self.recording.as_ref()
+            .map(|recording| &recording.settings)
+    }
+
+    // records a copy of the finished frame into a buffer,
+    // which can be read back once the frame has been submitted
+    pub(crate) fn copy_frame(&mut self,
+        device: &wgpu::Device,
+        config: &wgpu::SurfaceConfiguration,
+        frame_ctx: &mut FrameContext,
+    ) -> Option<FrameReadback> {
+        let mut paths = std::mem::take(&mut self.requests);
+        if let Some(path) = self.recording.as_mut().and_then(Recording::next_path) {
+            paths.push(path);
+        }
+
+        if paths.is_empty() {
+            return None;
+        }
+
+        // recordings are stopped, otherwise the error would be logged every frame
+        if !config.usage.contains(wgpu::TextureUsages::COPY_SRC) {
+            error!("{}", Error::CaptureUnsupported);
+            self.recording = None;
+            return None;
+        }
+
+        let texture = frame_ctx.texture();
+        let format = texture.format();
+        if !FrameReadback::is_supported(format) {
+            error!("{}", Error::CaptureFormat(format));
+            self.recording = None;
+            return None;
+        }
+
+        let width = texture.width();
+        let height = texture.height();
+
+        // buffer rows must be aligned, the padding is stripped when reading back
+        let unpadded_bytes_per_row = width * 4;
+        let alignment = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(alignment) * alignment;
+
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Frame Capture Buffer"),
+            size: (padded_bytes_per_row * height) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Frame Capture Encoder"),
+        });
+
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            texture.size(),
+        );
+
+        frame_ctx.add_encoder(encoder);
+
+        Some(FrameReadback {
+            buffer,
+            format,
+            width,
+            height,
+            padded_bytes_per_row,
+            paths,
+        })
+    }
+}
+
+pub(crate) struct FrameReadback {
+    buffer: wgpu::Buffer,
+    format: wgpu::TextureFormat,
+    width: u32,
+    height: u32,
+    padded_bytes_per_row: u32,
+    paths: Vec<PathBuf>,
+}
+
+impl FrameReadback {
+    fn is_supported(format: wgpu::TextureFormat) -> bool {
+        matches!(format,
+            wgpu::TextureFormat::Rgba8Unorm
+            | wgpu::TextureFormat::Rgba8UnormSrgb
+            | wgpu::TextureFormat::Bgra8Unorm
+            | wgpu::TextureFormat::Bgra8UnormSrgb
+        )
+    }
+
+    // blocks until the gpu is done with the frame
+    pub(crate) fn save(self, device: &wgpu::Device) -> Result<()> {
+        let slice = self.buffer.slice(..);
+
+        let (sender, receiver) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+
+        device.poll(wgpu::Maintain::Wait);
+        receiver.recv()
+            .unwrap_or(Err(wgpu::BufferAsyncError))?;
+
+        let image = {
+            let data = slice.get_mapped_range();
+            to_image(&data, self.format, self.width, self.height, self.padded_bytes_per_row)
+        };
+
+        self.buffer.unmap();
+
+        for path in &self.paths {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|source| Error::Io { path: parent.to_path_buf(), source })?;
+            }
+
+            image.save_with_format(path, image::ImageFormat::Png)
+                .map_err(|source| Error::SaveImage { path: path.clone(), source })?;
+        }
+
+        Ok(())
+    }
+}
+
+// drops the padding wgpu requires at the end of every row
+fn to_image(data: &[u8],
+    format: wgpu::TextureFormat,
+    width: u32,
+    height: u32,
+    padded_bytes_per_row: u32,
+) -> image::RgbaImage {
+    let is_bgra = matches!(format,
+        wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+    );
+
+    let unpadded_bytes_per_row = (width * 4) as usize;
+    let mut pixels = Vec::with_capacity(unpadded_bytes_per_row * height as usize);
+
+    for row in data.chunks(padded_bytes_per_row as usize) {
+        for pixel in row[..unpadded_bytes_per_row].chunks_exact(4) {
+            let (r, g, b) = if is_bgra {
+                (pixel[2], pixel[1], pixel[0])
+            } else {
+                (pixel[0], pixel[1], pixel[2])
+            };
+
+            // the surface alpha is meaningless when presenting opaquely
+            pixels.extend_from_slice(&[r, g, b, u8::MAX]);
+        }
+    }
+
+    image::RgbaImage::from_raw(width, height, pixels)
+        .expect("Frame capture buffer does not match the frame size")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn padding_is_dropped_and_bgra_is_swapped() {
+        // 3 pixels take 12 bytes, so every row is padded to 256
+        let (width, height, padded_bytes_per_row) = (3, 2, 256);
+
+        let mut data = vec![0xAA; (padded_bytes_per_row * height) as usize];
+        for y in 0..height {
+            for x in 0..width {
+                let offset = (y * padded_bytes_per_row + x * 4) as usize;
+                let value = (y * width + x) as u8;
+                data[offset..offset + 4].copy_from_slice(&[value, 100 + value, 200 + value, 7]);
+            }
+        }
+
+        let image = to_image(&data, wgpu::TextureFormat::Bgra8UnormSrgb, width, height, padded_bytes_per_row);
+
+        assert_eq!(image.dimensions(), (width, height));
+        assert_eq!(image.get_pixel(0, 0).0, [200, 100, 0, 255]);
+        assert_eq!(image.get_pixel(2, 1).0, [205, 105, 5, 255]);
+
+        let image = to_image(&data, wgpu::TextureFormat::Rgba8Unorm, width, height, padded_bytes_per_row);
+        assert_eq!(image.get_pixel(1, 1).0, [4, 104, 204, 255]);
+    }
+}
@@ -1,17 +1,36 @@
+use std::sync::Arc;
+
+use crate::{error::Result, Texture};
+
 pub struct FrameContext {
-    pub output: wgpu::SurfaceTexture,
+    pub output: Option<wgpu::SurfaceTexture>,
+    pub offscreen_texture: Option<Arc<Texture>>,
     pub view: wgpu::TextureView,
     pub encoders: Vec<wgpu::CommandEncoder>,
 }
 
 impl FrameContext {
-    pub fn new(surface: &wgpu::Surface) -> Self {
-        let output = surface.get_current_texture().unwrap();
+    pub fn new(surface: &wgpu::Surface) -> Result<Self> {
+        let output = surface.get_current_texture()?;
         let view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
         let encoders = Vec::new();
 
+        Ok(Self {
+            output: Some(output),
+            offscreen_texture: None,
+            view,
+            encoders,
+        })
+    }
+
+    pub fn offscreen(texture: Arc<Texture>) -> Self {
+        let view = texture.texture()
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let encoders = Vec::new();
+
         Self {
-            output,
+            output: None,
+            offscreen_texture: Some(texture),
             view,
             encoders,
         }
@@ -20,4 +39,19 @@ impl FrameContext {
     pub fn add_encoder(&mut self, encoder: wgpu::CommandEncoder) {
         self.encoders.push(encoder);
     }
+
+    pub fn texture(&self) -> &wgpu::Texture {
+        match (&self.output, &self.offscreen_texture) {
+            (Some(output), _) => &output.texture,
+            (None, Some(texture)) => texture.texture(),
+            (None, None) => unreachable!("Frame context has no render target"),
+        }
+    }
+
+    pub fn present(self) {
+        // offscreen frames are never presented
+        if let Some(output) = self.output {
+            output.present();
+        }
+    }
 }
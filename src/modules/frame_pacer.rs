@@ -0,0 +1,110 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use winit::event_loop::{ActiveEventLoop, ControlFlow};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FramePacing {
+    // redraws as soon as the previous frame is done
+    #[default]
+    Uncapped,
+    // sleeps and then spin-waits to hit the target precisely
+    TargetFps(u32),
+    // lets the event loop idle until the next frame is due,
+    // trading precision for cpu usage. useful for menus
+    LowPower(u32),
+    // only redraws after input or window events
+    Reactive,
+}
+
+#[derive(Debug)]
+pub struct FramePacer {
+    pacing: FramePacing,
+    next_frame: Instant,
+    redraw_requested: bool,
+}
+
+impl FramePacer {
+    // sleeping is imprecise, so the last part of the wait is spent spinning
+    const SPIN_MARGIN: Duration = Duration::from_millis(2);
+
+    pub fn new(pacing: FramePacing) -> Self {
+        let next_frame = Instant::now();
+        let redraw_requested = true;
+
+        Self {
+            pacing,
+            next_frame,
+            redraw_requested,
+        }
+    }
+
+    pub fn pacing(&self) -> FramePacing {
+        self.pacing
+    }
+
+    pub fn set_pacing(&mut self, pacing: FramePacing) {
+        self.pacing = pacing;
+        self.next_frame = Instant::now();
+        self.redraw_requested = true;
+    }
+
+    // wakes up the engine when running in reactive mode
+    pub fn request_redraw(&mut self) {
+        self.redraw_requested = true;
+    }
+
+    // sets how the event loop should wait and returns
+    // whether a new frame should be drawn right now
+    pub(crate) fn wait(&mut self, event_loop: &ActiveEventLoop) -> bool {
+        match self.pacing {
+            FramePacing::Uncapped => {
+                event_loop.set_control_flow(ControlFlow::Poll);
+                true
+            },
+            FramePacing::TargetFps(fps) => {
+                event_loop.set_control_flow(ControlFlow::Poll);
+                Self::sleep_until(self.next_frame);
+
+                let now = Instant::now();
+                self.next_frame = (self.next_frame + Self::frame_time(fps)).max(now);
+                true
+            },
+            FramePacing::LowPower(fps) => {
+                let now = Instant::now();
+                let should_draw = now >= self.next_frame;
+
+                if should_draw {
+                    self.next_frame = (self.next_frame + Self::frame_time(fps)).max(now);
+                }
+
+                event_loop.set_control_flow(ControlFlow::WaitUntil(self.next_frame));
+                should_draw
+            },
+            FramePacing::Reactive => {
+                event_loop.set_control_flow(ControlFlow::Wait);
+                std::mem::take(&mut self.redraw_requested)
+            },
+        }
+    }
+
+    fn frame_time(fps: u32) -> Duration {
+        Duration::from_secs_f64(1.0 / fps.max(1) as f64)
+    }
+
+    fn sleep_until(deadline: Instant) {
+        let now = Instant::now();
+        if deadline <= now {
+            return;
+        }
+
+        let remaining = deadline - now;
+        if remaining > Self::SPIN_MARGIN {
+            std::thread::sleep(remaining - Self::SPIN_MARGIN);
+        }
+
+        while Instant::now() < deadline {
+            std::hint::spin_loop();
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use glyphon::{Attrs, Buffer, Cache, Color, FontSystem, Metrics, Resolution, Shaping, SwashCache, TextArea, TextAtlas, TextBounds, TextRenderer, Viewport};
 use wgpu::{CommandEncoderDescriptor, Device, MultisampleState, Queue};
 
-use crate::modules::frame_context::FrameContext;
+use crate::{error::Result, modules::frame_context::FrameContext};
 
 #[derive(PartialEq, Eq, Clone, Copy)]
 pub struct LabelId(u32);
@@ -84,7 +84,7 @@ impl Label {
 }
 
 impl GlyphonRenderer {
-    pub fn new(device: &Device, queue: &Queue) -> Self {
+    pub fn new(device: &Device, queue: &Queue, format: wgpu::TextureFormat) -> Self {
         let font_system = FontSystem::new();
         let swash_cache = SwashCache::new();
         let cache = Cache::new(device);
@@ -92,7 +92,7 @@ impl GlyphonRenderer {
         let mut text_atlas = TextAtlas::new(device,
             queue,
             &cache,
-            wgpu::TextureFormat::Bgra8UnormSrgb
+            format
         );
         let renderer = TextRenderer::new(&mut text_atlas,
             device,
@@ -113,7 +113,7 @@ impl GlyphonRenderer {
         }
     }
 
-    pub fn prepare(&mut self, device: &Device, queue: &Queue) {
+    pub fn prepare(&mut self, device: &Device, queue: &Queue) -> Result<()> {
         self.renderer.prepare(device,
             queue,
             &mut self.font_system,
@@ -123,7 +123,9 @@ impl GlyphonRenderer {
                 .map(Label::get_area)
                 .collect::<Vec<TextArea>>(),
             &mut self.swash_cache
-        ).unwrap();
+        )?;
+
+        Ok(())
     }
 
     pub fn draw(&mut self,
@@ -131,7 +133,7 @@ impl GlyphonRenderer {
         queue: &wgpu::Queue,
         config: &wgpu::SurfaceConfiguration,
         frame_ctx: &mut FrameContext,
-    ) {
+    ) -> Result<()> {
         let view = &frame_ctx.view;
         let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
             label: Some("Glyphon Label Encoder"),
@@ -143,13 +145,14 @@ impl GlyphonRenderer {
                 height: config.height,
             });
 
-        self.prepare(device, queue);
+        self.prepare(device, queue)?;
 
         let mut pass = Self::pass(&mut encoder, view);
-        self.renderer.render(&self.text_atlas, &self.viewport, &mut pass)
-            .unwrap();
+        self.renderer.render(&self.text_atlas, &self.viewport, &mut pass)?;
+        drop(pass);
 
         frame_ctx.add_encoder(encoder);
+        Ok(())
     }
 
     pub fn add_label(&mut self, descriptor: LabelDescriptor) -> LabelId {
@@ -1,7 +1,11 @@
 use std::collections::HashMap;
 
+use bevy_ecs::system::Resource;
+use log::error;
 use winit::{event::ElementState, keyboard::KeyCode};
 
+use crate::error::{Error, Result};
+
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 pub enum KeyState {
     #[default]
@@ -21,7 +25,7 @@ impl From<ElementState> for KeyState {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Resource)]
 pub struct InputServer {
     mouse_delta: (f64, f64),
     action_map: HashMap<String, KeyCode>,
@@ -30,16 +34,18 @@ pub struct InputServer {
 
 impl InputServer {
     pub fn is_pressed(&self, action_name: &str) -> bool {
-        self.get_state(action_name) == KeyState::Pressed
+        self.get_state(action_name)
+            .inspect_err(|err| error!("{}", err))
+            .is_ok_and(|state| state == KeyState::Pressed)
     }
 
-    pub fn get_state(&self, action_name: &str) -> KeyState {
+    pub fn get_state(&self, action_name: &str) -> Result<KeyState> {
         let keycode = self.action_map.get(action_name)
-            .expect("Tried getting state for an unknown action");
+            .ok_or_else(|| Error::UnknownAction(action_name.to_string()))?;
 
-        match self.key_states.get(&keycode) {
-            None => KeyState::Released,
-            Some(state) => *state,
+        match self.key_states.get(keycode) {
+            None => Ok(KeyState::Released),
+            Some(state) => Ok(*state),
         }
     }
 
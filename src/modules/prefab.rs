@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+
+use bevy_ecs::{entity::Entity, world::World};
+use serde_json::Value;
+
+use crate::{components::{prefab::PrefabInstance, transform::Transform}, error::{Error, Result}, util};
+
+use super::{asset_server::Asset, scene_server::{Scene, SceneRegistry}};
+
+// replaces fields of the prefab's components, by entity index and component
+// name. objects are merged, so only the fields that are given are replaced
+#[derive(Debug, Clone, Default)]
+pub struct PrefabOverrides {
+    overrides: BTreeMap<(usize, String), Value>,
+}
+
+impl PrefabOverrides {
+    pub fn with(mut self, entity_idx: usize, component_name: &str, value: Value) -> Self {
+        self.overrides.insert((entity_idx, component_name.to_string()), value);
+        self
+    }
+}
+
+fn merge(target: &mut Value, value: Value) {
+    match (target, value) {
+        (Value::Object(target), Value::Object(value)) => {
+            for (key, value) in value {
+                merge(target.entry(key).or_insert(Value::Null), value);
+            }
+        },
+        (target, value) => *target = value,
+    }
+}
+
+// an entity tree stored with the scene format. the first entity is the
+// root, and every other entity without a parent is attached to it
+#[derive(Debug, Clone)]
+pub struct Prefab {
+    file_name: String,
+    scene: Scene,
+}
+
+impl Asset for Prefab {
+    fn file_name(&self) -> &str {
+        &self.file_name
+    }
+}
+
+impl Prefab {
+    pub fn load(file_name: &str) -> Result<Self> {
+        let bytes = util::load_bytes(file_name)?;
+        let json = String::from_utf8_lossy(&bytes);
+
+        Self::from_json(file_name, &json)
+    }
+
+    pub fn from_json(file_name: &str, json: &str) -> Result<Self> {
+        let file_name = file_name.to_string();
+        let scene = Scene::from_json(json)?;
+
+        Ok(Self {
+            file_name,
+            scene,
+        })
+    }
+
+    // spawns the entity tree with the root at the transform, and returns the root.
+    // overrides of the root's transform are merged into the given one
+    pub fn instantiate(&self,
+        world: &mut World,
+        registry: &SceneRegistry,
+        transform: Transform,
+        overrides: &PrefabOverrides,
+    ) -> Result<Entity> {
+        let mut scene = self.scene.clone();
+        if scene.entities.is_empty() {
+            return Err(Error::PrefabEntity { file_name: self.file_name.clone(), idx: 0 });
+        }
+
+        let transform = serde_json::to_value(transform)
+            .map_err(|source| Error::SceneComponent { name: "Transform".to_string(), source })?;
+        scene.entities[0].components.insert("Transform".to_string(), transform);
+
+        for ((entity_idx, component_name), value) in overrides.overrides.iter() {
+            let scene_entity = scene.entities.get_mut(*entity_idx)
+                .ok_or_else(|| Error::PrefabEntity { file_name: self.file_name.clone(), idx: *entity_idx })?;
+
+            let component = scene_entity.components
+                .entry(component_name.clone())
+                .or_insert(Value::Null);
+
+            merge(component, value.clone());
+        }
+
+        scene.entities[0].parent = None;
+        scene.entities.iter_mut()
+            .skip(1)
+            .filter(|scene_entity| scene_entity.parent.is_none())
+            .for_each(|scene_entity| scene_entity.parent = Some(0));
+
+        let entities = scene.spawn(world, registry)?;
+        let root = entities[0];
+
+        world.entity_mut(root)
+            .insert(PrefabInstance::new(&self.file_name));
+
+        Ok(root)
+    }
+}
@@ -136,8 +136,28 @@ impl RenderStorage {
     pub fn push_model(&mut self,
         model: &Model,
         device: &wgpu::Device
+    ) -> ModelId {
+        self.push_model_ex(model, true, device)
+    }
+
+    // the meshes are only drawn through mesh handles
+    pub fn push_model_without_instances(&mut self,
+        model: &Model,
+        device: &wgpu::Device
+    ) -> ModelId {
+        self.push_model_ex(model, false, device)
+    }
+
+    fn push_model_ex(&mut self,
+        model: &Model,
+        with_instances: bool,
+        device: &wgpu::Device
     ) -> ModelId {
         let mut meshes = model.meshes.clone();
+        if !with_instances {
+            meshes.iter_mut()
+                .for_each(|mesh| mesh.instances.clear());
+        }
 
         // convert model's texture ids to material ids
         for (i, texture) in model.textures.iter().enumerate() {
@@ -154,10 +174,25 @@ impl RenderStorage {
         self.push_meshes(&meshes, device)
     }
 
-    pub fn get_material(&self, material_id: MaterialId) -> &Material {
+    pub fn get_mesh(&self, mesh_id: MeshId) -> Option<&Mesh> {
+        self.meshes.iter()
+            .find(|mesh| mesh_id == mesh.mesh_id())
+    }
+
+    pub fn model_mesh_ids(&self, model_id: ModelId) -> Vec<MeshId> {
+        self.meshes.iter()
+            .filter(|mesh| mesh.model_id() == &Some(model_id))
+            .map(Mesh::mesh_id)
+            .collect()
+    }
+
+    pub fn has_material(&self, material_id: MaterialId) -> bool {
+        self.get_material(material_id).is_some()
+    }
+
+    pub fn get_material(&self, material_id: MaterialId) -> Option<&Material> {
         self.materials.iter()
             .find(|material| material_id == material.material_id())
-            .expect("Could not find material with the specified id")
     }
 
     pub fn multi_indexed_meshes(&self) -> &Vec<MultiIndexedMesh> {
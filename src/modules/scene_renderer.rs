@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+
+use bevy_ecs::world::World;
+use log::{debug, error};
+
+use crate::{components::{renderable::{MaterialHandle, MeshHandle, Visibility}, transform::GlobalTransform}, pass_ext::VoxDrawPassExt, render::material::Material, EngineInternal, InstanceRaw};
+
+use super::{frame_context::FrameContext, render_storage::{MaterialId, MeshId, RenderStorage}};
+
+// screens may push meshes without pushing their materials
+fn get_material(render_storage: &RenderStorage, material_id: MaterialId) -> Option<&Material> {
+    let material = render_storage.get_material(material_id);
+    if material.is_none() {
+        debug!("Material {} is not in the render storage.", material_id);
+    }
+
+    material
+}
+
+struct InstanceBatch {
+    buffer: wgpu::Buffer,
+    capacity: usize,
+    count: usize,
+}
+
+impl InstanceBatch {
+    fn new(device: &wgpu::Device, capacity: usize) -> Self {
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Entity Instance Buffer"),
+            size: (capacity * std::mem::size_of::<InstanceRaw>()) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let count = 0;
+
+        Self {
+            buffer,
+            capacity,
+            count,
+        }
+    }
+
+    fn write(&mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        instances: &[InstanceRaw],
+    ) {
+        // grown to a power of two, so that buffers are rarely recreated
+        if instances.len() > self.capacity {
+            *self = Self::new(device, instances.len().next_power_of_two());
+        }
+
+        queue.write_buffer(&self.buffer, 0, bytemuck::cast_slice(instances));
+        self.count = instances.len();
+    }
+}
+
+// draws render storages with the default pipeline before any screen draws.
+// screens that record their own passes should load, not clear, the frame
+pub struct SceneRenderer {
+    enabled: bool,
+    // the instances of renderable entities, by mesh and material
+    batches: HashMap<(MeshId, MaterialId), InstanceBatch>,
+}
+
+impl Default for SceneRenderer {
+    fn default() -> Self {
+        let enabled = true;
+        let batches = HashMap::new();
+
+        Self {
+            enabled,
+            batches,
+        }
+    }
+}
+
+impl SceneRenderer {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    // when disabled, screens are responsible for clearing the frame
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    // the entity instances packed by the last prepare
+    pub fn instance_count(&self) -> usize {
+        self.batches.values()
+            .map(|batch| batch.count)
+            .sum()
+    }
+
+    // packs the global transforms of visible entities with a mesh handle
+    // into instance buffers, once per frame. the meshes and materials
+    // are looked up in the engine's render storage
+    pub fn prepare(&mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        render_storage: &RenderStorage,
+        world: &mut World,
+    ) {
+        if !self.enabled {
+            return;
+        }
+
+        let mut instances = HashMap::<(MeshId, MaterialId), Vec<InstanceRaw>>::new();
+        let mut query = world.query::<(&MeshHandle, Option<&MaterialHandle>, Option<&Visibility>, &GlobalTransform)>();
+
+        for (mesh_handle, material_handle, visibility, global_transform) in query.iter(world) {
+            if visibility == Some(&Visibility::Hidden) {
+                continue;
+            }
+
+            let Some(mesh) = render_storage.get_mesh(mesh_handle.0) else {
+                debug!("Mesh {} is not in the render storage.", mesh_handle.0);
+                continue;
+            };
+
+            let material_id = material_handle.map_or(mesh.material_id(), |material_handle| material_handle.0);
+            if get_material(render_storage, material_id).is_none() {
+                continue;
+            }
+
+            instances.entry((mesh_handle.0, material_id))
+                .or_default()
+                .push(InstanceRaw::from(global_transform));
+        }
+
+        self.batches.retain(|key, _| instances.contains_key(key));
+
+        for (key, instances) in instances {
+            self.batches.entry(key)
+                .or_insert_with(|| InstanceBatch::new(device, instances.len()))
+                .write(device, queue, &instances);
+        }
+    }
+
+    pub fn draw<'a>(&self,
+        engine_internal: &'a EngineInternal,
+        render_storages: impl Iterator<Item = &'a RenderStorage>,
+        frame_ctx: &mut FrameContext,
+    ) {
+        if !self.enabled {
+            return;
+        }
+
+        let device = &engine_internal.device;
+        let default_pipeline = &engine_internal.default_pipeline;
+        let capabilities = &engine_internal.capabilities;
+        let depth_view = engine_internal.depth_texture.view();
+
+        let camera_bind_group = match default_pipeline.camera_bind_group() {
+            Ok(camera_bind_group) => camera_bind_group,
+            Err(err) => {
+                error!("Skipping scene: {}", err);
+                return;
+            }
+        };
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Scene Encoder"),
+        });
+
+        let mut pass = default_pipeline.pass(&mut encoder, &frame_ctx.view, depth_view);
+
+        let render_storages = std::iter::once(&engine_internal.render_storage)
+            .chain(render_storages);
+
+        for render_storage in render_storages {
+            // empty buffers cannot be bound
+            render_storage.meshes()
+                .iter()
+                .filter(|mesh| mesh.num_instances() > 0)
+                .for_each(|mesh| {
+                    if let Some(material) = get_material(render_storage, mesh.material_id()) {
+                        pass.draw_mesh(mesh, material, camera_bind_group);
+                    }
+                });
+
+            render_storage.multi_indexed_meshes()
+                .iter()
+                .filter(|mesh| mesh.draw_count() > 0)
+                .for_each(|mesh| {
+                    if let Some(material) = get_material(render_storage, mesh.material_id()) {
+                        pass.draw_mesh_multi_indexed(mesh, material, camera_bind_group, capabilities);
+                    }
+                });
+        }
+
+        let render_storage = &engine_internal.render_storage;
+        for (&(mesh_id, material_id), batch) in self.batches.iter() {
+            let Some(mesh) = render_storage.get_mesh(mesh_id) else {
+                continue;
+            };
+
+            let Some(material) = get_material(render_storage, material_id) else {
+                continue;
+            };
+
+            pass.draw_mesh_instanced(mesh,
+                material,
+                &batch.buffer,
+                0..batch.count as u32,
+                camera_bind_group
+            );
+        }
+
+        drop(pass);
+        frame_ctx.add_encoder(encoder);
+    }
+}
@@ -0,0 +1,322 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use bevy_ecs::{prelude::*, world::{EntityRef, EntityWorldMut}};
+use log::{error, warn};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{components::{camera::{ActiveCamera, CameraComponent}, hierarchy::Parent, prefab::PrefabInstance, kinematics::{Acceleration, AngularVelocity, Velocity}, renderable::{MaterialHandle, MaterialRef, MeshHandle, MeshRef, Visibility}, transform::Transform}, error::{Error, Result}, render::model::Model, Texture};
+
+use super::{asset_server::AssetServer, render_storage::{MaterialId, MeshId, RenderStorage}};
+
+struct ComponentRegistration {
+    save: fn(&EntityRef) -> Option<serde_json::Result<Value>>,
+    load: fn(&mut EntityWorldMut, Value) -> serde_json::Result<()>,
+}
+
+fn save_component<T: Component + Serialize>(entity: &EntityRef) -> Option<serde_json::Result<Value>> {
+    entity.get::<T>()
+        .map(serde_json::to_value)
+}
+
+fn load_component<T: Component + DeserializeOwned>(entity: &mut EntityWorldMut,
+    value: Value,
+) -> serde_json::Result<()> {
+    let component = serde_json::from_value::<T>(value)?;
+    entity.insert(component);
+    Ok(())
+}
+
+// the components that are saved to scenes, by the name they are saved with.
+// derived components, such as global transforms, are left out
+pub struct SceneRegistry {
+    components: BTreeMap<String, ComponentRegistration>,
+}
+
+impl Default for SceneRegistry {
+    fn default() -> Self {
+        let components = BTreeMap::new();
+        let mut registry = Self {
+            components,
+        };
+
+        registry.register::<Transform>("Transform");
+        registry.register::<Visibility>("Visibility");
+        registry.register::<MeshRef>("MeshRef");
+        registry.register::<MaterialRef>("MaterialRef");
+        registry.register::<Velocity>("Velocity");
+        registry.register::<AngularVelocity>("AngularVelocity");
+        registry.register::<Acceleration>("Acceleration");
+        registry.register::<CameraComponent>("Camera");
+        registry.register::<ActiveCamera>("ActiveCamera");
+        registry.register::<PrefabInstance>("PrefabInstance");
+
+        registry
+    }
+}
+
+impl SceneRegistry {
+    pub fn register<T>(&mut self, name: &str)
+    where
+        T: Component + Serialize + DeserializeOwned,
+    {
+        let registration = ComponentRegistration {
+            save: save_component::<T>,
+            load: load_component::<T>,
+        };
+
+        self.components.insert(name.to_string(), registration);
+    }
+
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.components.contains_key(name)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SceneEntity {
+    // the index of the parent in the scene's entities
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<usize>,
+    #[serde(default)]
+    pub components: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Scene {
+    pub entities: Vec<SceneEntity>,
+}
+
+impl Scene {
+    // saves every entity with at least one registered component
+    pub fn from_world(world: &World, registry: &SceneRegistry) -> Result<Self> {
+        let mut saved = Vec::new();
+
+        for entity in world.iter_entities() {
+            let mut components = BTreeMap::new();
+
+            for (name, registration) in registry.components.iter() {
+                if let Some(value) = (registration.save)(&entity) {
+                    let value = value.map_err(|source| Error::SceneComponent { name: name.clone(), source })?;
+                    components.insert(name.clone(), value);
+                }
+            }
+
+            if !components.is_empty() {
+                saved.push((entity.id(), components));
+            }
+        }
+
+        // sorted, so that saving the same world twice gives the same scene
+        saved.sort_by_key(|(entity, _)| *entity);
+
+        let indices = saved.iter()
+            .enumerate()
+            .map(|(idx, (entity, _))| (*entity, idx))
+            .collect::<HashMap<_, _>>();
+
+        let entities = saved.into_iter()
+            .map(|(entity, components)| {
+                // parents that are not saved leave the entity at the root
+                let parent = world.get::<Parent>(entity)
+                    .and_then(|parent| indices.get(&parent.get()))
+                    .copied();
+
+                SceneEntity {
+                    parent,
+                    components,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            entities,
+        })
+    }
+
+    // returns the spawned entities, in the order they appear in the scene.
+    // nothing is spawned if the scene is invalid
+    pub fn spawn(&self, world: &mut World, registry: &SceneRegistry) -> Result<Vec<Entity>> {
+        if let Some(parent) = self.entities.iter()
+            .filter_map(|scene_entity| scene_entity.parent)
+            .find(|parent| *parent >= self.entities.len())
+        {
+            return Err(Error::SceneParent(parent));
+        }
+
+        let entities = self.entities.iter()
+            .map(|_| world.spawn_empty().id())
+            .collect::<Vec<_>>();
+
+        if let Err(err) = self.insert_components(world, registry, &entities) {
+            entities.iter()
+                .for_each(|entity| { world.despawn(*entity); });
+
+            return Err(err);
+        }
+
+        Ok(entities)
+    }
+
+    fn insert_components(&self,
+        world: &mut World,
+        registry: &SceneRegistry,
+        entities: &[Entity],
+    ) -> Result<()> {
+        for (scene_entity, entity) in self.entities.iter().zip(entities) {
+            let mut entity_mut = world.entity_mut(*entity);
+
+            for (name, value) in scene_entity.components.iter() {
+                let Some(registration) = registry.components.get(name) else {
+                    warn!("Skipping unregistered scene component {}.", name);
+                    continue;
+                };
+
+                (registration.load)(&mut entity_mut, value.clone())
+                    .map_err(|source| Error::SceneComponent { name: name.clone(), source })?;
+            }
+
+            if let Some(parent) = scene_entity.parent {
+                entity_mut.insert(Parent(entities[parent]));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(Error::Scene)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(Error::Scene)
+    }
+}
+
+#[derive(Default)]
+pub struct SceneServer {
+    registry: SceneRegistry,
+    // the meshes pushed for every model, in the model's order
+    model_meshes: HashMap<String, Vec<MeshId>>,
+    materials: HashMap<String, MaterialId>,
+    // assets that could not be loaded are not retried every frame
+    failed: HashSet<String>,
+}
+
+impl SceneServer {
+    pub fn register<T>(&mut self, name: &str)
+    where
+        T: Component + Serialize + DeserializeOwned,
+    {
+        self.registry.register::<T>(name);
+    }
+
+    pub fn registry(&self) -> &SceneRegistry {
+        &self.registry
+    }
+
+    pub fn save(&self, world: &World) -> Result<String> {
+        Scene::from_world(world, &self.registry)?
+            .to_json()
+    }
+
+    pub fn load(&self, world: &mut World, json: &str) -> Result<Vec<Entity>> {
+        Scene::from_json(json)?
+            .spawn(world, &self.registry)
+    }
+
+    // gives mesh and material handles to entities that only reference
+    // their assets by file name, such as the ones loaded from scenes
+    pub(crate) fn resolve_assets(&mut self,
+        asset_server: &mut AssetServer,
+        render_storage: &mut RenderStorage,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        world: &mut World,
+    ) {
+        let mut mesh_query = world.query_filtered::<(Entity, &MeshRef), Without<MeshHandle>>();
+        let mesh_refs = mesh_query.iter(world)
+            .map(|(entity, mesh_ref)| (entity, mesh_ref.clone()))
+            .collect::<Vec<_>>();
+
+        for (entity, mesh_ref) in mesh_refs {
+            let Some(mesh_ids) = self.model_meshes(&mesh_ref.file_name, asset_server, render_storage, device, queue) else {
+                continue;
+            };
+
+            match mesh_ids.get(mesh_ref.mesh_index) {
+                Some(mesh_id) => { world.entity_mut(entity).insert(MeshHandle(*mesh_id)); },
+                None => {
+                    let mesh_name = format!("{}#{}", mesh_ref.file_name, mesh_ref.mesh_index);
+                    if self.failed.insert(mesh_name) {
+                        error!("Model {} has no mesh {}.", mesh_ref.file_name, mesh_ref.mesh_index);
+                    }
+                },
+            }
+        }
+
+        let mut material_query = world.query_filtered::<(Entity, &MaterialRef), Without<MaterialHandle>>();
+        let material_refs = material_query.iter(world)
+            .map(|(entity, material_ref)| (entity, material_ref.clone()))
+            .collect::<Vec<_>>();
+
+        for (entity, material_ref) in material_refs {
+            if let Some(material_id) = self.material(&material_ref.file_name, asset_server, render_storage, device, queue) {
+                world.entity_mut(entity).insert(MaterialHandle(material_id));
+            }
+        }
+    }
+
+    fn model_meshes(&mut self,
+        file_name: &str,
+        asset_server: &mut AssetServer,
+        render_storage: &mut RenderStorage,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Option<&Vec<MeshId>> {
+        if !self.model_meshes.contains_key(file_name) {
+            if self.failed.contains(file_name) {
+                return None;
+            }
+
+            let Ok(model) = asset_server.get_or_load::<Model>(file_name, device, queue) else {
+                self.failed.insert(file_name.to_string());
+                return None;
+            };
+
+            let model_id = render_storage.push_model_without_instances(&model, device);
+            let mesh_ids = render_storage.model_mesh_ids(model_id);
+            self.model_meshes.insert(file_name.to_string(), mesh_ids);
+        }
+
+        self.model_meshes.get(file_name)
+    }
+
+    fn material(&mut self,
+        file_name: &str,
+        asset_server: &mut AssetServer,
+        render_storage: &mut RenderStorage,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Option<MaterialId> {
+        if let Some(material_id) = self.materials.get(file_name) {
+            return Some(*material_id);
+        }
+
+        if self.failed.contains(file_name) {
+            return None;
+        }
+
+        let Ok(texture) = asset_server.get_or_load::<Texture>(file_name, device, queue) else {
+            self.failed.insert(file_name.to_string());
+            return None;
+        };
+
+        let material_id = render_storage.push_material(texture, device);
+        self.materials.insert(file_name.to_string(), material_id);
+
+        Some(material_id)
+    }
+}
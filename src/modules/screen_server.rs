@@ -1,75 +1,130 @@
-use std::collections::HashMap;
+use std::{collections::{HashMap, VecDeque}, sync::Arc};
 
-use log::debug;
+use bevy_ecs::schedule::{ExecutorKind, Schedule, SystemConfigs};
+use log::{debug, warn};
 
-use crate::{screens::screen::Screen, EngineInternal};
+use crate::{screens::screen::Screen, EngineInternal, Texture};
 
-use super::commands::Commands;
-
-#[derive(Clone, Copy, Default, Eq, PartialEq, Hash, Debug)]
-pub enum GameState {
-    #[default]
-    Menu,
-    Game,
-}
+use super::{commands::Commands, event_bus::{StateChanged, TimerFired}, frame_context::FrameContext, screen_transition::ScreenTransition, states::{StateKey, States}, timer_server::TimerAction};
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 enum Cycle {
-    Start,
+    Enter,
+    Exit,
     Ui,
     Draw,
     Update,
+    Pause,
+    Resume,
 }
 
-// TODO make this generic like Action
+#[derive(Debug, Clone)]
+pub enum StateTransition {
+    // clears the stack and enters the state
+    Set(StateKey),
+    // covers the current state, pausing it
+    Push(StateKey),
+    // uncovers the state beneath, resuming it
+    Pop,
+    // swaps the topmost state without resuming the one beneath
+    Replace(StateKey),
+}
 
-pub struct ScreenServer {
-    state: GameState,
-    next_state: Option<GameState>,
-    registered_screens: HashMap<GameState, Vec<Box<dyn Screen>>>,
+struct ScreenEntry {
+    screen: Box<dyn Screen>,
+    initialized: bool,
+    update_schedule: Option<Schedule>,
+    draw_schedule: Option<Schedule>,
 }
 
-impl Default for ScreenServer {
-    fn default() -> Self {
-        let next_state = Some(GameState::default());
-        let state = GameState::default();
-        let registered_screens = HashMap::default();
+impl ScreenEntry {
+    fn new(screen: impl Screen) -> Self {
+        let update_schedule = to_schedule(screen.update_systems());
+        let draw_schedule = to_schedule(screen.draw_systems());
+        let screen = Box::new(screen);
+        let initialized = false;
 
         Self {
-            next_state,
-            state,
-            registered_screens,
+            screen,
+            initialized,
+            update_schedule,
+            draw_schedule,
         }
     }
 }
 
+// systems run one after another on the calling thread, so no task pool is needed
+fn to_schedule(systems: Option<SystemConfigs>) -> Option<Schedule> {
+    systems.map(|systems| {
+        let mut schedule = Schedule::default();
+        schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        schedule.add_systems(systems);
+        schedule
+    })
+}
+
+#[derive(Clone)]
+struct ActiveTransition {
+    transition: ScreenTransition,
+    elapsed: f32,
+    // captured once, right before the transition was applied
+    outgoing: Arc<Texture>,
+    incoming: Arc<Texture>,
+}
+
+#[derive(Default)]
+pub struct ScreenServer {
+    // the topmost state is the current one
+    stack: Vec<StateKey>,
+    // applied in order before the next update or draw
+    transitions: VecDeque<(StateTransition, Option<ScreenTransition>)>,
+    active_transition: Option<ActiveTransition>,
+    registered_screens: HashMap<StateKey, Vec<ScreenEntry>>,
+}
+
 impl ScreenServer {
-    pub fn draw(&mut self, engine_internal: &mut EngineInternal) {
-        if self.should_state_change() {
-            self.update_state();
-            self.emit_event(Cycle::Start, engine_internal);
+    pub fn draw(&mut self,
+        engine_internal: &mut EngineInternal,
+        frame_ctx: &mut FrameContext,
+    ) {
+        self.apply_transitions(engine_internal);
+
+        while matches!(self.transitions.front(), Some((_, Some(_)))) {
+            let Some((transition, Some(screen_transition))) = self.transitions.pop_front() else {
+                break;
+            };
+
+            self.begin_transition(transition, screen_transition, engine_internal, frame_ctx);
+            self.apply_transitions(engine_internal);
+        }
+
+        match self.active_transition.clone() {
+            Some(active_transition) => self.draw_transition(active_transition, engine_internal, frame_ctx),
+            None => self.draw_layers(engine_internal, frame_ctx),
         }
 
-        self.emit_event(Cycle::Draw, engine_internal);
         self.emit_event(Cycle::Ui, engine_internal);
     }
 
     pub fn update(&mut self, engine_internal: &mut EngineInternal) {
-        if self.should_state_change() {
-            self.update_state();
-            self.emit_event(Cycle::Start, engine_internal);
-        }
+        self.apply_transitions(engine_internal);
 
         self.emit_event(Cycle::Update, engine_internal);
     }
 
-    pub fn register_screen(&mut self, screen: impl Screen, state: GameState) {
-        let screen = Box::new(screen);
+    pub fn register_screen<S: States>(&mut self, screen: impl Screen, state: S) {
+        let screen = ScreenEntry::new(screen);
+        let state = StateKey::new(state);
+
+        // the first registered state is used unless another one is set
+        if self.stack.is_empty() && self.transitions.is_empty() {
+            self.transitions.push_back((StateTransition::Set(state.clone()), None));
+        }
 
         match self.registered_screens.get_mut(&state) {
             Some(vec) => vec.push(screen),
             None => {
-                let vec: Vec<Box<dyn Screen>> = vec![screen];
+                let vec = vec![screen];
                 self.registered_screens.insert(state, vec);
             }
         }
@@ -80,40 +135,308 @@ impl ScreenServer {
         cycle: Cycle,
         engine_internal: &mut EngineInternal
     ) {
-        let screens_opt = self.registered_screens
-            .get_mut(&self.state);
+        let mut commands = Commands::new(engine_internal);
+        self.emit_with(cycle, &mut commands);
+    }
+
+    fn emit_with(&mut self, cycle: Cycle, commands: &mut Commands) {
+        let first_layer = match cycle {
+            Cycle::Update => self.first_layer(|screen| screen.update_below()),
+            Cycle::Draw | Cycle::Ui => self.first_layer(|screen| screen.draw_below()),
+            Cycle::Enter | Cycle::Exit | Cycle::Pause | Cycle::Resume => self.stack.len().saturating_sub(1),
+        };
+
+        for state in self.stack.iter().skip(first_layer) {
+            let Some(screens) = self.registered_screens.get_mut(state) else {
+                debug!("Game state {:?} has no screens registered.", state);
+                continue;
+            };
+
+            commands.state = Some(state.clone());
 
-        if screens_opt.is_none() {
-            debug!("Game state has no screens registered.");
+            screens.iter_mut()
+                .for_each(|ScreenEntry { screen, initialized, update_schedule, draw_schedule }| {
+                    match cycle {
+                        Cycle::Enter => {
+                            if !*initialized {
+                                screen.init(commands);
+                                *initialized = true;
+
+                                if let Some(mut schedule) = to_schedule(screen.start_systems()) {
+                                    commands.engine_internal.run_schedule(&mut schedule);
+                                }
+                            }
+
+                            screen.on_enter(commands);
+                        },
+                        Cycle::Exit => screen.on_exit(commands),
+                        Cycle::Draw => {
+                            screen.draw(commands);
+
+                            if let Some(schedule) = draw_schedule {
+                                commands.engine_internal.run_schedule(schedule);
+                            }
+                        },
+                        Cycle::Update => {
+                            screen.update(commands);
+
+                            if let Some(schedule) = update_schedule {
+                                commands.engine_internal.run_schedule(schedule);
+                            }
+                        },
+                        Cycle::Ui => screen.ui(commands),
+                        Cycle::Pause => screen.on_pause(commands),
+                        Cycle::Resume => screen.on_resume(commands),
+                    }
+                });
+        }
+
+        commands.state = None;
+        self.transitions.extend(commands.transitions.drain(..));
+    }
+
+    pub(crate) fn fire_timers(&mut self, engine_internal: &mut EngineInternal) {
+        let mut timers = engine_internal.timer_server.take_due();
+        if timers.is_empty() {
             return;
         }
 
         let mut commands = Commands::new(engine_internal);
+        for timer in timers.iter_mut() {
+            // timers started from the callback share its owner
+            commands.state = timer.owner().cloned();
+            let timer_id = timer.timer_id();
 
-        let screens = screens_opt.unwrap();
-        screens.iter_mut()
-            .for_each(|screen| {
-                match cycle {
-                    Cycle::Start => screen.start(&mut commands),
-                    Cycle::Draw => screen.draw(&mut commands),
-                    Cycle::Update => screen.update(&mut commands),
-                    Cycle::Ui => screen.ui(&mut commands),
-                }
-            });
+            match timer.action_mut() {
+                TimerAction::Callback(callback) => callback(&mut commands),
+                TimerAction::Event(event_name) => {
+                    let event_name = event_name.clone();
+                    commands.send(TimerFired { timer_id, event_name });
+                },
+            }
+        }
 
-        self.next_state = commands.new_state;
+        self.transitions.extend(commands.transitions);
+        engine_internal.timer_server.restore(timers);
     }
 
-    pub fn state(&self) -> GameState {
-        self.state
+    // the scene is drawn first, so that screens can draw on top of it
+    fn draw_layers(&mut self,
+        engine_internal: &mut EngineInternal,
+        frame_ctx: &mut FrameContext,
+    ) {
+        let first_layer = self.first_layer(|screen| screen.draw_below());
+        let render_storages = self.stack.iter()
+            .skip(first_layer)
+            .filter_map(|state| self.registered_screens.get(state))
+            .flatten()
+            .filter_map(|entry| entry.screen.render_storage());
+
+        engine_internal.scene_renderer
+            .draw(engine_internal, render_storages, frame_ctx);
+
+        let mut commands = Commands::with_frame(engine_internal, frame_ctx);
+        self.emit_with(Cycle::Draw, &mut commands);
+    }
+
+    // draws the visible states into the target instead of the frame,
+    // the work is still submitted together with the frame
+    fn draw_offscreen(&mut self,
+        engine_internal: &mut EngineInternal,
+        target: Arc<Texture>,
+        frame_ctx: &mut FrameContext,
+    ) {
+        let mut offscreen_ctx = FrameContext::offscreen(target);
+        self.draw_layers(engine_internal, &mut offscreen_ctx);
+        frame_ctx.encoders.append(&mut offscreen_ctx.encoders);
     }
 
-    fn update_state(&mut self) {
-        self.state = self.next_state.unwrap();
-        self.next_state = None;
+    fn begin_transition(&mut self,
+        transition: StateTransition,
+        screen_transition: ScreenTransition,
+        engine_internal: &mut EngineInternal,
+        frame_ctx: &mut FrameContext,
+    ) {
+        let outgoing = Texture::render_target(&engine_internal.device, &engine_internal.config);
+        self.draw_offscreen(engine_internal, outgoing.clone(), frame_ctx);
+
+        self.apply_transition(transition, engine_internal);
+
+        let incoming = Texture::render_target(&engine_internal.device, &engine_internal.config);
+        self.active_transition = Some(ActiveTransition {
+            transition: screen_transition,
+            elapsed: 0.0,
+            outgoing,
+            incoming,
+        });
     }
 
-    fn should_state_change(&self) -> bool {
-        self.next_state.is_some()
+    fn draw_transition(&mut self,
+        mut active_transition: ActiveTransition,
+        engine_internal: &mut EngineInternal,
+        frame_ctx: &mut FrameContext,
+    ) {
+        let device = &engine_internal.device;
+        let config = &engine_internal.config;
+
+        // the incoming screens share the depth texture, so their sizes must match
+        let incoming_size = active_transition.incoming.texture().size();
+        if incoming_size.width != config.width || incoming_size.height != config.height {
+            active_transition.incoming = Texture::render_target(device, config);
+        }
+
+        let incoming = active_transition.incoming.clone();
+        self.draw_offscreen(engine_internal, incoming, frame_ctx);
+
+        active_transition.elapsed += engine_internal.time.delta();
+        let transition = active_transition.transition;
+        let progress = transition.progress(active_transition.elapsed);
+
+        engine_internal.transition_renderer.draw(&engine_internal.device,
+            &engine_internal.queue,
+            [&active_transition.outgoing, &active_transition.incoming],
+            transition.effect,
+            progress,
+            frame_ctx
+        );
+
+        self.active_transition = if active_transition.elapsed >= transition.duration {
+            None
+        } else {
+            Some(active_transition)
+        };
+    }
+
+    // walks down the stack until a state hides the ones beneath it
+    fn first_layer(&self, passes_below: impl Fn(&dyn Screen) -> bool) -> usize {
+        let mut first_layer = self.stack.len().saturating_sub(1);
+
+        while first_layer > 0 {
+            let passes = self.registered_screens.get(&self.stack[first_layer])
+                .is_none_or(|screens| screens.iter().all(|entry| passes_below(entry.screen.as_ref())));
+
+            if !passes {
+                break;
+            }
+
+            first_layer -= 1;
+        }
+
+        first_layer
+    }
+
+    pub fn frame_dropped(&mut self,
+        engine_internal: &mut EngineInternal,
+        error: &wgpu::SurfaceError,
+    ) {
+        let mut commands = Commands::new(engine_internal);
+
+        for state in self.stack.iter() {
+            let Some(screens) = self.registered_screens.get_mut(state) else {
+                continue;
+            };
+
+            commands.state = Some(state.clone());
+            screens.iter_mut()
+                .for_each(|entry| entry.screen.frame_dropped(&mut commands, error));
+        }
+
+        self.transitions.extend(commands.transitions);
+    }
+
+    pub fn is_transitioning(&self) -> bool {
+        self.active_transition.is_some()
+    }
+
+    pub fn state(&self) -> Option<&StateKey> {
+        self.stack.last()
+    }
+
+    pub fn stack(&self) -> &[StateKey] {
+        &self.stack
+    }
+
+    // the states that are drawn this frame, from the bottom up
+    pub fn visible_states(&self) -> &[StateKey] {
+        let first_layer = self.first_layer(|screen| screen.draw_below());
+        &self.stack[first_layer.min(self.stack.len())..]
+    }
+
+    // returns none if the current state is of another type
+    pub fn current_state<S: States>(&self) -> Option<S> {
+        self.state()
+            .and_then(|state| state.get::<S>())
+    }
+
+    pub fn is_state<S: States>(&self, state: S) -> bool {
+        self.state()
+            .is_some_and(|current| current.is(state))
+    }
+
+    // changes state before the next update or draw
+    pub fn set_state<S: States>(&mut self, state: S) {
+        self.transitions.push_back((StateTransition::Set(StateKey::new(state)), None));
+    }
+
+    // transitions requested while entering or exiting a state are
+    // queued and applied right after. animated transitions wait for
+    // the next draw, which captures the outgoing screens first
+    fn apply_transitions(&mut self, engine_internal: &mut EngineInternal) {
+        while let Some((_, None)) = self.transitions.front() {
+            let Some((transition, _)) = self.transitions.pop_front() else {
+                break;
+            };
+
+            self.apply_transition(transition, engine_internal);
+        }
+    }
+
+    fn apply_transition(&mut self,
+        transition: StateTransition,
+        engine_internal: &mut EngineInternal,
+    ) {
+        let previous = self.state().cloned();
+
+        match transition {
+            StateTransition::Set(state) => {
+                while !self.stack.is_empty() {
+                    self.exit_state(engine_internal);
+                }
+
+                self.stack.push(state);
+                self.emit_event(Cycle::Enter, engine_internal);
+            },
+            StateTransition::Push(state) => {
+                self.emit_event(Cycle::Pause, engine_internal);
+                self.stack.push(state);
+                self.emit_event(Cycle::Enter, engine_internal);
+            },
+            StateTransition::Pop => {
+                if self.stack.len() <= 1 {
+                    warn!("Cannot pop the last game state.");
+                    return;
+                }
+
+                self.exit_state(engine_internal);
+                self.emit_event(Cycle::Resume, engine_internal);
+            },
+            StateTransition::Replace(state) => {
+                self.exit_state(engine_internal);
+                self.stack.push(state);
+                self.emit_event(Cycle::Enter, engine_internal);
+            },
+        }
+
+        let current = self.state().cloned();
+        engine_internal.event_bus.send(StateChanged { previous, current });
+    }
+
+    // exits and removes the topmost state, along with its timers
+    fn exit_state(&mut self, engine_internal: &mut EngineInternal) {
+        self.emit_event(Cycle::Exit, engine_internal);
+
+        if let Some(state) = self.stack.pop() {
+            engine_internal.timer_server.cancel_owned(&state);
+        }
     }
 }
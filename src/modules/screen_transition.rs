@@ -0,0 +1,96 @@
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    #[default]
+    EaseInOut,
+}
+
+impl Easing {
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+// the direction the incoming screen is revealed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl WipeDirection {
+    // in texture coordinates, where y points down
+    pub fn vector(&self) -> [f32; 2] {
+        match self {
+            WipeDirection::Left => [-1.0, 0.0],
+            WipeDirection::Right => [1.0, 0.0],
+            WipeDirection::Up => [0.0, -1.0],
+            WipeDirection::Down => [0.0, 1.0],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransitionEffect {
+    // fades the outgoing screen to a colour, then the colour to the incoming screen
+    Fade([f32; 3]),
+    Crossfade,
+    Wipe(WipeDirection),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScreenTransition {
+    pub effect: TransitionEffect,
+    // in seconds
+    pub duration: f32,
+    pub easing: Easing,
+}
+
+impl ScreenTransition {
+    pub fn new(effect: TransitionEffect, duration: f32) -> Self {
+        let easing = Easing::default();
+
+        Self {
+            effect,
+            duration,
+            easing,
+        }
+    }
+
+    pub fn fade(color: [f32; 3], duration: f32) -> Self {
+        Self::new(TransitionEffect::Fade(color), duration)
+    }
+
+    pub fn crossfade(duration: f32) -> Self {
+        Self::new(TransitionEffect::Crossfade, duration)
+    }
+
+    pub fn wipe(direction: WipeDirection, duration: f32) -> Self {
+        Self::new(TransitionEffect::Wipe(direction), duration)
+    }
+
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    // eased progress after the given time, from 0 to 1
+    pub fn progress(&self, elapsed: f32) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+
+        self.easing.apply(elapsed / self.duration)
+    }
+}
@@ -0,0 +1,73 @@
+use std::{any::{Any, TypeId}, fmt::Debug, hash::{DefaultHasher, Hash, Hasher}, sync::Arc};
+
+// implemented by the app for any type describing the states it can be in
+pub trait States: Copy + Eq + Hash + Debug + Send + Sync + 'static {}
+
+// a type-erased state, so that apps can use their own state types
+#[derive(Clone)]
+pub struct StateKey {
+    type_id: TypeId,
+    hash: u64,
+    value: Arc<dyn Any + Send + Sync>,
+    // compares the values, as different states can share a hash
+    eq: fn(&dyn Any, &dyn Any) -> bool,
+    name: Arc<str>,
+}
+
+fn state_eq<S: States>(a: &dyn Any, b: &dyn Any) -> bool {
+    a.downcast_ref::<S>() == b.downcast_ref::<S>()
+}
+
+impl StateKey {
+    pub fn new<S: States>(state: S) -> Self {
+        let type_id = TypeId::of::<S>();
+        let hash = {
+            let mut hasher = DefaultHasher::new();
+            state.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let value = Arc::new(state);
+        let eq = state_eq::<S>;
+        let name = format!("{:?}", state).into();
+
+        Self {
+            type_id,
+            hash,
+            value,
+            eq,
+            name,
+        }
+    }
+
+    // returns none if the state is of another type
+    pub fn get<S: States>(&self) -> Option<S> {
+        self.value.downcast_ref::<S>()
+            .copied()
+    }
+
+    pub fn is<S: States>(&self, state: S) -> bool {
+        self.get::<S>() == Some(state)
+    }
+}
+
+impl PartialEq for StateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.type_id == other.type_id && (self.eq)(&*self.value, &*other.value)
+    }
+}
+
+impl Eq for StateKey {}
+
+impl Hash for StateKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.type_id.hash(state);
+        self.hash.hash(state);
+    }
+}
+
+impl Debug for StateKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
@@ -0,0 +1,167 @@
+use std::time::Instant;
+
+use bevy_ecs::system::Resource;
+
+#[derive(Debug, Clone, Resource)]
+pub struct Time {
+    last_frame: Instant,
+    delta: f32,
+    scaled_delta: f32,
+    fixed_delta: f32,
+    // replaces the measured frame delta, such as when recording
+    fixed_frame_delta: Option<f32>,
+    accumulator: f32,
+    elapsed: f32,
+    scaled_elapsed: f32,
+    frame_count: u64,
+    tick_count: u64,
+    time_scale: f32,
+    paused: bool,
+    step_requested: bool,
+}
+
+impl Time {
+    // avoids a burst of updates after the engine has been idle,
+    // such as when paced reactively or after a breakpoint
+    const MAX_FRAME_DELTA: f32 = 0.25;
+
+    pub fn new(fixed_delta: f32) -> Self {
+        let last_frame = Instant::now();
+        let time_scale = 1.0;
+
+        Self {
+            last_frame,
+            fixed_delta,
+            time_scale,
+            delta: 0.0,
+            fixed_frame_delta: None,
+            scaled_delta: 0.0,
+            accumulator: 0.0,
+            elapsed: 0.0,
+            scaled_elapsed: 0.0,
+            frame_count: 0,
+            tick_count: 0,
+            paused: false,
+            step_requested: false,
+        }
+    }
+
+    pub(crate) fn advance_frame(&mut self) {
+        let delta = match self.fixed_frame_delta {
+            Some(fixed_frame_delta) => fixed_frame_delta,
+            None => self.last_frame.elapsed().as_secs_f32(),
+        };
+
+        self.advance_frame_by(delta);
+    }
+
+    pub(crate) fn advance_frame_by(&mut self, delta: f32) {
+        self.delta = delta;
+        self.last_frame = Instant::now();
+
+        self.scaled_delta = if self.paused {
+            0.0
+        } else {
+            self.delta.min(Self::MAX_FRAME_DELTA) * self.time_scale
+        };
+
+        self.elapsed += self.delta;
+        self.scaled_elapsed += self.scaled_delta;
+        self.accumulator += self.scaled_delta;
+    }
+
+    // returns true for every fixed update that should run this frame
+    pub(crate) fn consume_tick(&mut self) -> bool {
+        if self.step_requested {
+            self.step_requested = false;
+            return true;
+        }
+
+        if self.paused || self.accumulator < self.fixed_delta {
+            return false;
+        }
+
+        self.accumulator -= self.fixed_delta;
+        true
+    }
+
+    pub(crate) fn count_tick(&mut self) {
+        self.tick_count += 1;
+    }
+
+    pub(crate) fn count_frame(&mut self) {
+        self.frame_count += 1;
+    }
+
+    pub fn delta(&self) -> f32 {
+        self.delta
+    }
+
+    pub fn scaled_delta(&self) -> f32 {
+        self.scaled_delta
+    }
+
+    pub fn fixed_delta(&self) -> f32 {
+        self.fixed_delta
+    }
+
+    // how far the current frame is between the last
+    // fixed update and the next one, from 0 to 1
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator / self.fixed_delta).clamp(0.0, 1.0)
+    }
+
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub fn scaled_elapsed(&self) -> f32 {
+        self.scaled_elapsed
+    }
+
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    pub fn tick_count(&self) -> u64 {
+        self.tick_count
+    }
+
+    pub fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    pub fn fixed_frame_delta(&self) -> Option<f32> {
+        self.fixed_frame_delta
+    }
+
+    // makes every frame advance time by the same amount, regardless of wall-clock time
+    pub fn set_fixed_frame_delta(&mut self, fixed_frame_delta: Option<f32>) {
+        self.fixed_frame_delta = fixed_frame_delta;
+    }
+
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        self.time_scale = time_scale.max(0.0);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    // runs exactly one fixed update on the next frame, even while paused
+    pub fn step(&mut self) {
+        self.step_requested = true;
+    }
+}
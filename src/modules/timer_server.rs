@@ -0,0 +1,152 @@
+use super::{commands::Commands, states::StateKey};
+
+pub type TimerId = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimerDuration {
+    // fixed updates, which stop while the game is paused
+    Ticks(u64),
+    // wall-clock seconds, unaffected by pausing or time scale
+    Seconds(f32),
+}
+
+pub enum TimerAction {
+    Callback(Box<dyn FnMut(&mut Commands)>),
+    Event(String),
+}
+
+pub(crate) struct Timer {
+    timer_id: TimerId,
+    duration: TimerDuration,
+    remaining: TimerDuration,
+    repeating: bool,
+    // cancelled when this state exits
+    owner: Option<StateKey>,
+    action: TimerAction,
+}
+
+impl Timer {
+    pub(crate) fn timer_id(&self) -> TimerId {
+        self.timer_id
+    }
+
+    pub(crate) fn owner(&self) -> Option<&StateKey> {
+        self.owner.as_ref()
+    }
+
+    pub(crate) fn action_mut(&mut self) -> &mut TimerAction {
+        &mut self.action
+    }
+
+    fn is_due(&self) -> bool {
+        match self.remaining {
+            TimerDuration::Ticks(ticks) => ticks == 0,
+            TimerDuration::Seconds(seconds) => seconds <= 0.0,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TimerServer {
+    timers: Vec<Timer>,
+    // timers cancelled while their action was running
+    cancelled: Vec<TimerId>,
+    free_timer_id: TimerId,
+}
+
+impl TimerServer {
+    pub fn add(&mut self,
+        duration: TimerDuration,
+        repeating: bool,
+        owner: Option<StateKey>,
+        action: TimerAction,
+    ) -> TimerId {
+        let timer_id = self.free_timer_id;
+        let timer = Timer {
+            timer_id,
+            duration,
+            remaining: duration,
+            repeating,
+            owner,
+            action,
+        };
+
+        self.timers.push(timer);
+        self.free_timer_id += 1;
+
+        timer_id
+    }
+
+    pub fn cancel(&mut self, timer_id: TimerId) {
+        self.timers.retain(|timer| timer.timer_id != timer_id);
+        self.cancelled.push(timer_id);
+    }
+
+    pub fn cancel_owned(&mut self, owner: &StateKey) {
+        self.timers.retain(|timer| timer.owner.as_ref() != Some(owner));
+    }
+
+    pub fn is_active(&self, timer_id: TimerId) -> bool {
+        self.timers.iter()
+            .any(|timer| timer.timer_id == timer_id)
+    }
+
+    pub(crate) fn advance_ticks(&mut self) {
+        self.timers.iter_mut()
+            .for_each(|timer| {
+                if let TimerDuration::Ticks(ticks) = &mut timer.remaining {
+                    *ticks = ticks.saturating_sub(1);
+                }
+            });
+    }
+
+    pub(crate) fn advance_seconds(&mut self, delta: f32) {
+        self.timers.iter_mut()
+            .for_each(|timer| {
+                if let TimerDuration::Seconds(seconds) = &mut timer.remaining {
+                    *seconds -= delta;
+                }
+            });
+    }
+
+    // removes the timers that are due, so that their actions can be run
+    pub(crate) fn take_due(&mut self) -> Vec<Timer> {
+        let (due, pending) = std::mem::take(&mut self.timers)
+            .into_iter()
+            .partition(Timer::is_due);
+
+        self.timers = pending;
+        self.cancelled.clear();
+
+        due
+    }
+
+    // puts back repeating timers once their actions have run
+    pub(crate) fn restore(&mut self, timers: Vec<Timer>) {
+        for timer in timers {
+            if !self.cancelled.contains(&timer.timer_id) {
+                self.rearm(timer);
+            }
+        }
+
+        self.cancelled.clear();
+    }
+
+    fn rearm(&mut self, mut timer: Timer) {
+        if !timer.repeating {
+            return;
+        }
+
+        timer.remaining = match (timer.duration, timer.remaining) {
+            // a zero duration would fire every time timers are checked
+            (TimerDuration::Ticks(ticks), _) => TimerDuration::Ticks(ticks.max(1)),
+            // the overshoot is carried over so that the timer does not drift
+            (TimerDuration::Seconds(seconds), TimerDuration::Seconds(remaining)) => {
+                TimerDuration::Seconds(remaining + seconds)
+            },
+            (duration, _) => duration,
+        };
+
+        self.timers.push(timer);
+    }
+}
@@ -1,14 +1,26 @@
-use crate::render::{material::Material, mesh::Mesh, multi_indexed_mesh::MultiIndexedMesh};
+use std::ops::Range;
+
+use wgpu::util::DrawIndexedIndirectArgs;
+
+use crate::render::{gpu_capabilities::GpuCapabilities, material::Material, mesh::Mesh, multi_indexed_mesh::MultiIndexedMesh};
 
 pub trait VoxDrawPassExt {
     fn draw_mesh(&mut self,
         mesh: &Mesh,
         material: &Material,
         camera_bind_group: &wgpu::BindGroup);
+    fn draw_mesh_instanced(&mut self,
+        mesh: &Mesh,
+        material: &Material,
+        instance_buffer: &wgpu::Buffer,
+        instances: Range<u32>,
+        camera_bind_group: &wgpu::BindGroup,
+    );
     fn draw_mesh_multi_indexed(&mut self,
         mesh: &MultiIndexedMesh,
         material: &Material,
         camera_bind_group: &wgpu::BindGroup,
+        capabilities: &GpuCapabilities,
     );
 }
 
@@ -17,25 +29,43 @@ impl VoxDrawPassExt for wgpu::RenderPass<'_> {
         mesh: &Mesh,
         material: &Material,
         camera_bind_group: &wgpu::BindGroup,
+    ) {
+        let instance_buffer = mesh.instance_buffer();
+        let num_instances = mesh.num_instances() as u32;
+
+        self.draw_mesh_instanced(mesh,
+            material,
+            instance_buffer,
+            0..num_instances,
+            camera_bind_group
+        );
+    }
+
+    // draws the mesh with instances that are not its own
+    fn draw_mesh_instanced(&mut self,
+        mesh: &Mesh,
+        material: &Material,
+        instance_buffer: &wgpu::Buffer,
+        instances: Range<u32>,
+        camera_bind_group: &wgpu::BindGroup,
     ) {
         let vertex_buffer = mesh.vertex_buffer();
         let index_buffer = mesh.index_buffer();
-        let instance_buffer = mesh.instance_buffer();
         let num_indices = mesh.num_indices() as u32;
-        let num_instances = mesh.num_instances() as u32;
 
         self.set_vertex_buffer(0, vertex_buffer.slice(..));
         self.set_vertex_buffer(1, instance_buffer.slice(..));
         self.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
         self.set_bind_group(0, material.bind_group(), &[]);
         self.set_bind_group(1, camera_bind_group, &[]);
-        self.draw_indexed(0..num_indices, 0, 0..num_instances);
+        self.draw_indexed(0..num_indices, 0, instances);
     }
 
     fn draw_mesh_multi_indexed(&mut self,
         mesh: &MultiIndexedMesh,
         material: &Material,
         camera_bind_group: &wgpu::BindGroup,
+        capabilities: &GpuCapabilities,
     ) {
         let vertex_buffer = mesh.vertex_buffer();
         let index_buffer = mesh.index_buffer();
@@ -48,9 +78,25 @@ impl VoxDrawPassExt for wgpu::RenderPass<'_> {
         self.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
         self.set_bind_group(0, material.bind_group(), &[]);
         self.set_bind_group(1, camera_bind_group, &[]);
-        self.multi_draw_indexed_indirect(indirect_buffer,
-            0,
-            draw_count
-        );
+
+        if capabilities.supports_multi_draw_indirect() {
+            self.multi_draw_indexed_indirect(indirect_buffer,
+                0,
+                draw_count
+            );
+        } else if capabilities.supports_draw_indirect() {
+            let stride = std::mem::size_of::<DrawIndexedIndirectArgs>() as wgpu::BufferAddress;
+
+            for i in 0..draw_count {
+                self.draw_indexed_indirect(indirect_buffer, i as wgpu::BufferAddress * stride);
+            }
+        } else {
+            for args in mesh.indirect_indexed_args() {
+                let indices = args.first_index..args.first_index + args.index_count;
+                let instances = args.first_instance..args.first_instance + args.instance_count;
+
+                self.draw_indexed(indices, args.base_vertex, instances);
+            }
+        }
     }
 }
@@ -8,3 +8,5 @@ pub mod model_mesh;
 pub mod multi_indexed_mesh;
 pub mod camera;
 pub mod pipeline_system;
+pub mod gpu_capabilities;
+pub mod interpolated;
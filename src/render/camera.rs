@@ -2,6 +2,8 @@ use cgmath::{Angle, Deg, Matrix4, Point3, Rad, Vector3};
 
 use crate::modules::input_server::InputServer;
 
+use super::interpolated::Interpolate;
+
 #[rustfmt::skip]
 const OPENGL_TO_WGPU_MATRIX: cgmath::Matrix4<f32> = cgmath::Matrix4::new(
     1.0, 0.0, 0.0, 0.0,
@@ -12,6 +14,23 @@ const OPENGL_TO_WGPU_MATRIX: cgmath::Matrix4<f32> = cgmath::Matrix4::new(
 
 pub type CameraUniform = [[f32 ; 4] ; 4];
 
+// shared by the camera transform and the camera component
+pub(crate) fn view_projection(position: Point3<f32>,
+    target: Point3<f32>,
+    up: Vector3<f32>,
+    fovy: f32,
+    aspect: f32,
+    znear: f32,
+    zfar: f32,
+) -> CameraUniform {
+    let view = Matrix4::look_at_rh(position, target, up);
+    let proj = cgmath::perspective(Deg(fovy), aspect, znear, zfar);
+
+    (OPENGL_TO_WGPU_MATRIX * proj * view)
+        .into()
+}
+
+#[derive(Debug, Clone, Copy)]
 pub struct CameraTransform {
     position: Point3<f32>,
     target: Point3<f32>,
@@ -50,21 +69,26 @@ impl CameraTransform {
     }
 
     pub fn uniform(&self) -> CameraUniform {
-        let view = Matrix4::look_at_rh(
-            self.position,
+        view_projection(self.position,
             self.target,
-            self.up
-        );
-        
-        let proj = cgmath::perspective(
-            Deg(self.fovy),
+            self.up,
+            self.fovy,
             self.aspect,
             self.znear,
             self.zfar
-        );
+        )
+    }
+}
 
-        (OPENGL_TO_WGPU_MATRIX * proj * view)
-            .into()
+impl Interpolate for CameraTransform {
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self {
+        Self {
+            position: self.position + (other.position - self.position) * alpha,
+            target: self.target + (other.target - self.target) * alpha,
+            yaw: self.yaw.interpolate(&other.yaw, alpha),
+            pitch: self.pitch.interpolate(&other.pitch, alpha),
+            ..*other
+        }
     }
 }
 
@@ -83,8 +107,8 @@ impl FpsCamera {
         }
     }
 
-    pub fn update(&mut self, input_server: &InputServer) {
-        self.update_position(input_server);
+    pub fn update(&mut self, input_server: &InputServer, delta: f32) {
+        self.update_position(input_server, delta);
 
         let mouse_delta = input_server.mouse_delta();
         self.update_view(mouse_delta);
@@ -111,33 +135,33 @@ impl FpsCamera {
         ).into();
     }
 
-    // todo add delta time
     // todo add relative movement with cross product
-    fn update_position(&mut self, input_server: &InputServer) {
+    fn update_position(&mut self, input_server: &InputServer, delta: f32) {
         let transform = &mut self.transform;
+        let speed = self.speed * delta;
 
         if input_server.is_pressed("camera_up") {
-            transform.position.y += self.speed;
+            transform.position.y += speed;
         }
 
         if input_server.is_pressed("camera_right") {
-            transform.position.x += self.speed;
+            transform.position.x += speed;
         }
 
         if input_server.is_pressed("camera_down") {
-            transform.position.y -= self.speed;
+            transform.position.y -= speed;
         }
 
         if input_server.is_pressed("camera_left") {
-            transform.position.y -= self.speed;
+            transform.position.y -= speed;
         }
 
         if input_server.is_pressed("camera_front") {
-            transform.position.z += self.speed;
+            transform.position.z += speed;
         }
 
         if input_server.is_pressed("camera_back") {
-            transform.position.z -= self.speed;
+            transform.position.z -= speed;
         }
     }
 
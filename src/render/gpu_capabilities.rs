@@ -0,0 +1,49 @@
+use log::warn;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct GpuCapabilities {
+    pub polygon_mode_line: bool,
+    pub multi_draw_indirect: bool,
+    pub indirect_first_instance: bool,
+    pub indirect_execution: bool,
+}
+
+impl GpuCapabilities {
+    // requested only when the adapter supports them
+    pub const OPTIONAL_FEATURES: wgpu::Features = wgpu::Features::POLYGON_MODE_LINE
+        .union(wgpu::Features::MULTI_DRAW_INDIRECT)
+        .union(wgpu::Features::INDIRECT_FIRST_INSTANCE);
+
+    pub fn supported_features(adapter: &wgpu::Adapter) -> wgpu::Features {
+        let features = adapter.features() & Self::OPTIONAL_FEATURES;
+
+        let missing = Self::OPTIONAL_FEATURES - features;
+        if !missing.is_empty() {
+            warn!("Adapter does not support {:?}, falling back where possible.", missing);
+        }
+
+        features
+    }
+
+    pub fn new(adapter: &wgpu::Adapter, device: &wgpu::Device) -> Self {
+        let features = device.features();
+        let downlevel_flags = adapter.get_downlevel_capabilities().flags;
+
+        Self {
+            polygon_mode_line: features.contains(wgpu::Features::POLYGON_MODE_LINE),
+            multi_draw_indirect: features.contains(wgpu::Features::MULTI_DRAW_INDIRECT),
+            indirect_first_instance: features.contains(wgpu::Features::INDIRECT_FIRST_INSTANCE),
+            indirect_execution: downlevel_flags.contains(wgpu::DownlevelFlags::INDIRECT_EXECUTION),
+        }
+    }
+
+    // indirect args can have a non zero first instance,
+    // so every indirect path also needs INDIRECT_FIRST_INSTANCE
+    pub fn supports_multi_draw_indirect(&self) -> bool {
+        self.multi_draw_indirect && self.indirect_first_instance
+    }
+
+    pub fn supports_draw_indirect(&self) -> bool {
+        self.indirect_execution && self.indirect_first_instance
+    }
+}
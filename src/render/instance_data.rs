@@ -1,9 +1,11 @@
 use bytemuck::{Pod, Zeroable};
 use cgmath::{Quaternion, Vector3, Zero};
 
+use crate::components::transform::GlobalTransform;
+
 use super::{mesh::MeshPosition, pipeline_system::AsVertexBufferLayout};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct InstanceData {
     pub position: Vector3<f32>,
     pub rotation: Quaternion<f32>,
@@ -42,6 +44,14 @@ pub struct InstanceRaw {
     model: [[f32; 4]; 4],
 }
 
+impl From<&GlobalTransform> for InstanceRaw {
+    fn from(global_transform: &GlobalTransform) -> Self {
+        Self {
+            model: global_transform.matrix().into(),
+        }
+    }
+}
+
 impl AsVertexBufferLayout for InstanceRaw {
     fn desc() -> wgpu::VertexBufferLayout<'static> {
         wgpu::VertexBufferLayout {
@@ -0,0 +1,92 @@
+use cgmath::{InnerSpace, VectorSpace};
+
+use crate::InstanceData;
+
+pub trait Interpolate {
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self;
+}
+
+// keeps the state of the last two fixed updates,
+// so that draws in between can be smoothed
+#[derive(Debug, Clone)]
+pub struct Interpolated<T> {
+    previous: T,
+    current: T,
+}
+
+impl<T: Interpolate + Clone> Interpolated<T> {
+    pub fn new(value: T) -> Self {
+        let previous = value.clone();
+        let current = value;
+
+        Self {
+            previous,
+            current,
+        }
+    }
+
+    // should be called once per update
+    pub fn push(&mut self, value: T) {
+        self.previous = std::mem::replace(&mut self.current, value);
+    }
+
+    // moves without interpolating, such as when teleporting
+    pub fn reset(&mut self, value: T) {
+        self.previous = value.clone();
+        self.current = value;
+    }
+
+    pub fn get(&self, alpha: f32) -> T {
+        self.previous.interpolate(&self.current, alpha)
+    }
+
+    pub fn previous(&self) -> &T {
+        &self.previous
+    }
+
+    pub fn current(&self) -> &T {
+        &self.current
+    }
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self {
+        self + (other - self) * alpha
+    }
+}
+
+impl Interpolate for InstanceData {
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self {
+        let position = self.position.lerp(other.position, alpha);
+
+        // nlerp would divide by zero on the zero quaternions
+        // used by instances that were never rotated
+        let rotation = if self.rotation == other.rotation
+            || self.rotation.magnitude2() == 0.0
+            || other.rotation.magnitude2() == 0.0
+        {
+            other.rotation
+        } else {
+            self.rotation.nlerp(other.rotation, alpha)
+        };
+
+        Self {
+            position,
+            rotation,
+        }
+    }
+}
+
+impl<T: Interpolate + Clone> Interpolate for Vec<T> {
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self {
+        // instances were added or removed, nothing to blend with
+        if self.len() != other.len() {
+            return other.clone();
+        }
+
+        self.iter()
+            .zip(other.iter())
+            .map(|(previous, current)| previous.interpolate(current, alpha))
+            .collect()
+    }
+}
@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
-use crate::{modules::asset_server::{Asset, AssetServer}, InstanceData, Texture};
+use log::error;
+
+use crate::{error::{Error, Result}, modules::asset_server::{Asset, AssetServer}, InstanceData, Texture};
 
 use super::{model_mesh::ModelMesh, vertex::Vertex};
 
@@ -22,23 +24,23 @@ impl Model {
         asset_server: &mut AssetServer,
         device: &wgpu::Device,
         queue: &wgpu::Queue
-    ) -> Model {
+    ) -> Result<Model> {
         let (models, materials_opt) = tobj::load_obj(file_name, &tobj::GPU_LOAD_OPTIONS)
-            .expect("Could not load file OBJ file");
+            .map_err(|source| Error::Obj { file_name: file_name.to_string(), source })
+            .inspect_err(|err| error!("{}", err))?;
 
         let textures: Vec<Arc<Texture>> = match materials_opt {
             Ok(tobj_materials) => {
                 tobj_materials
                     .into_iter()
-                    .map(|m| {
-                        let diffuse_texture_name = &m.diffuse_texture.unwrap();
-                        asset_server
-                            .get_or_load(diffuse_texture_name, device, queue)
-                            .unwrap()
-                    }).collect::<Vec<_>>()
+                    .map(|m| match m.diffuse_texture {
+                        Some(diffuse_texture_name) => asset_server
+                            .get_or_load(&diffuse_texture_name, device, queue),
+                        None => Texture::debug(asset_server, device, queue),
+                    }).collect::<Result<Vec<_>>>()?
             },
             Err(_) => {
-                let diffuse_texture = Texture::debug(asset_server, device, queue);
+                let diffuse_texture = Texture::debug(asset_server, device, queue)?;
                 vec![diffuse_texture]
             }
         };
@@ -85,10 +87,10 @@ impl Model {
                 }
             }).collect::<Vec<_>>();
 
-        Self {
+        Ok(Self {
             name: file_name.to_string(),
             textures,
             meshes,
-        }
+        })
     }
 }
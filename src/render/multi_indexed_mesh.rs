@@ -18,6 +18,8 @@ pub struct MultiIndexedMesh {
     index_buffer: wgpu::Buffer,
     instance_buffer: wgpu::Buffer,
     indirect_indexed_buffer: wgpu::Buffer,
+    // kept around to issue plain draws when indirect ones are unsupported
+    indirect_indexed_args: Vec<DrawIndexedIndirectArgs>,
     draw_count: u32,
     material_id: MaterialId,
     mesh_id: MultiIndexedMeshId,
@@ -40,12 +42,14 @@ impl MultiIndexedMesh {
         let instance_buffer = device.compute_instance_buffer(&instances);
         let indirect_indexed_buffer = device
             .compute_indirect_indexed_buffer(indirect_indexed_args);
+        let indirect_indexed_args = indirect_indexed_args.to_vec();
 
         Self {
             vertex_buffer,
             instance_buffer,
             index_buffer,
             indirect_indexed_buffer,
+            indirect_indexed_args,
             material_id,
             mesh_id,
             model_id,
@@ -77,6 +81,10 @@ impl MultiIndexedMesh {
         &self.indirect_indexed_buffer
     }
 
+    pub fn indirect_indexed_args(&self) -> &[DrawIndexedIndirectArgs] {
+        &self.indirect_indexed_args
+    }
+
     pub fn material_id(&self) -> MaterialId {
         self.material_id
     }
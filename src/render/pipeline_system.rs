@@ -1,6 +1,6 @@
 use log::debug;
 
-use crate::Texture;
+use crate::{error::{Error, Result}, Texture};
 
 pub type UniformId = usize;
 pub type LayoutId = usize;
@@ -135,11 +135,19 @@ impl Pipeline {
         self.render_pipeline.as_ref().unwrap()
     }
 
-    pub fn buffer(&self, idx: UniformId) -> &wgpu::Buffer {
+    pub fn buffer(&self, idx: UniformId) -> Result<&wgpu::Buffer> {
         self.uniforms.get(idx)
-            .expect("Could not find uniform with specified index")
+            .ok_or(Error::MissingUniform(idx))?
             .buffer
             .as_ref()
-            .expect("Found a shader uniform, but it doesnt have a buffer set!")
+            .ok_or(Error::MissingUniformBuffer(idx))
+    }
+
+    pub fn bind_group(&self, idx: UniformId) -> Result<&wgpu::BindGroup> {
+        self.uniforms.get(idx)
+            .ok_or(Error::MissingUniform(idx))?
+            .bind_group
+            .as_ref()
+            .ok_or(Error::MissingUniformBindGroup(idx))
     }
 }
@@ -1,11 +1,13 @@
 use std::sync::Arc;
 
 use image::GenericImageView;
-use crate::{modules::asset_server::{Asset, AssetServer}, util::load_bytes};
+use log::error;
+
+use crate::{error::{Error, Result}, modules::asset_server::{Asset, AssetServer}, util::load_bytes};
 
 #[derive(Debug)]
 pub struct Texture {
-    _texture: wgpu::Texture,
+    texture: wgpu::Texture,
     view: wgpu::TextureView,
     sampler: wgpu::Sampler,
     name: String,
@@ -24,10 +26,9 @@ impl Texture {
     pub fn debug(asset_server: &mut AssetServer,
         device: &wgpu::Device,
         queue: &wgpu::Queue
-    ) -> Arc<Texture> {
+    ) -> Result<Arc<Texture>> {
         asset_server
             .get_or_load("debug.png", device, queue)
-            .unwrap()
     }
 
     pub fn from_bytes(
@@ -35,11 +36,12 @@ impl Texture {
         queue: &wgpu::Queue,
         bytes: &[u8],
         file_name: &str,
-    ) -> Texture {
+    ) -> Result<Texture> {
         let img = image::load_from_memory(bytes)
-            .expect("Could not load texture from bytes!");
+            .map_err(|source| Error::Image { file_name: file_name.to_string(), source })
+            .inspect_err(|err| error!("{}", err))?;
 
-        Self::from_image(device, queue, &img, file_name)
+        Ok(Self::from_image(device, queue, &img, file_name))
     }
  
     pub fn from_image(
@@ -99,7 +101,7 @@ impl Texture {
         let name = file_name.to_string();
 
         Self {
-            _texture: texture,
+            texture,
             view,
             sampler,
             name,
@@ -110,8 +112,8 @@ impl Texture {
         file_name: &str,
         device: &wgpu::Device,
         queue: &wgpu::Queue
-    ) -> Texture {
-        let data = load_bytes(file_name);
+    ) -> Result<Texture> {
+        let data = load_bytes(file_name)?;
         Texture::from_bytes(device, queue, &data, file_name)
     }
  
@@ -156,7 +158,7 @@ impl Texture {
         let name = name.to_string();
  
         let texture = Self {
-            _texture: texture,
+            texture,
             view,
             sampler,
             name,
@@ -165,6 +167,59 @@ impl Texture {
         Arc::new(texture)
     }
 
+    pub fn render_target(
+        device: &wgpu::Device,
+        config: &wgpu::SurfaceConfiguration,
+    ) -> Arc<Texture> {
+        let name = "render_target";
+
+        let size = wgpu::Extent3d {
+            width: config.width,
+            height: config.height,
+            depth_or_array_layers: 1,
+        };
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(name),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: config.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        let name = name.to_string();
+
+        let texture = Self {
+            texture,
+            view,
+            sampler,
+            name,
+        };
+
+        Arc::new(texture)
+    }
+
+    pub fn texture(&self) -> &wgpu::Texture {
+        &self.texture
+    }
+
     pub fn view(&self) -> &wgpu::TextureView {
         &self.view
     }
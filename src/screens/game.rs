@@ -9,14 +9,6 @@
 //
 //use super::screen::Screen;
 //
-//#[rustfmt::skip]
-//const OPENGL_TO_WGPU_MATRIX: cgmath::Matrix4<f32> = cgmath::Matrix4::new(
-//    1.0, 0.0, 0.0, 0.0,
-//    0.0, 1.0, 0.0, 0.0,
-//    0.0, 0.0, 0.5, 0.5,
-//    0.0, 0.0, 0.0, 1.0,
-//);
-//
 //#[derive(Default)]
 //pub struct GameScreen {
 //    label_id: Option<LabelId>,
@@ -55,7 +47,7 @@
 //    }
 //
 //    fn draw_systems(&self) -> Option<SystemConfigs> {
-//        self.to_systems((draw_objects, draw_camera))
+//        self.to_systems(draw_objects)
 //    }
 //
 //    fn game_state(&self) -> GameState {
@@ -203,30 +195,3 @@
 //
 //    frame_ctx.add_encoder(encoder);
 //}
-//
-//// TODO: move this engine side
-//pub fn draw_camera(query: Query<&CameraComponent>,
-//    render_ctx: Res<RenderContext>,
-//    pipeline: Res<DefaultPipeline>,
-//) {
-//    for camera_cmpnt in &query {
-//        let view = Matrix4::look_at_rh(
-//            camera_cmpnt.position,
-//            camera_cmpnt.target,
-//            camera_cmpnt.up
-//        );
-//
-//        let proj = cgmath::perspective(
-//            cgmath::Deg(camera_cmpnt.fovy),
-//            camera_cmpnt.aspect,
-//            camera_cmpnt.znear,
-//            camera_cmpnt.zfar
-//        );
-//
-//        let uniform: CameraUniform = (OPENGL_TO_WGPU_MATRIX * proj * view)
-//            .into();
-//
-//        render_ctx.queue.write_buffer(pipeline.camera_buffer(),
-//            0, bytemuck::cast_slice(&uniform));
-//    }
-//}
@@ -1,9 +1,41 @@
-use crate::modules::commands::Commands;
+use bevy_ecs::schedule::{IntoSystemConfigs, SystemConfigs};
+
+use crate::modules::{commands::Commands, render_storage::RenderStorage};
 
 #[allow(unused_variables)]
 pub trait Screen where Self: 'static {
-    fn start(&mut self, commands: &mut Commands) {}
+    // called once, the first time the screen's state is entered
+    fn init(&mut self, commands: &mut Commands) {}
+    // called every time the screen's state is set, pushed or replaced in
+    fn on_enter(&mut self, commands: &mut Commands) {}
+    // called every time the screen's state is removed from the stack
+    fn on_exit(&mut self, commands: &mut Commands) {}
     fn ui(&mut self, commands: &mut Commands) {}
     fn draw(&mut self, commands: &mut Commands) {}
     fn update(&mut self, commands: &mut Commands) {}
+    // called when a frame could not be acquired and was skipped
+    fn frame_dropped(&mut self, commands: &mut Commands, error: &wgpu::SurfaceError) {}
+    // called when another state is pushed on top of this one
+    fn on_pause(&mut self, commands: &mut Commands) {}
+    // called when the state on top of this one is popped
+    fn on_resume(&mut self, commands: &mut Commands) {}
+    // whether the states beneath this one keep updating while it is on top
+    fn update_below(&self) -> bool { false }
+    // whether the states beneath this one are still drawn, such as for overlays
+    fn draw_below(&self) -> bool { false }
+    // drawn by the engine with the default pipeline before draw is called
+    fn render_storage(&self) -> Option<&RenderStorage> { None }
+    // run once against the engine world, right after init
+    fn start_systems(&self) -> Option<SystemConfigs> { None }
+    // run against the engine world after every update
+    fn update_systems(&self) -> Option<SystemConfigs> { None }
+    // run against the engine world after every draw
+    fn draw_systems(&self) -> Option<SystemConfigs> { None }
+
+    fn to_systems<M>(&self, systems: impl IntoSystemConfigs<M>) -> Option<SystemConfigs>
+    where
+        Self: Sized,
+    {
+        Some(systems.into_configs())
+    }
 }
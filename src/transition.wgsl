@@ -0,0 +1,61 @@
+struct TransitionUniform {
+    color: vec4<f32>,
+    direction: vec2<f32>,
+    progress: f32,
+    effect: u32,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@group(0) @binding(0)
+var t_outgoing: texture_2d<f32>;
+@group(0) @binding(1)
+var t_incoming: texture_2d<f32>;
+@group(0) @binding(2)
+var s_screen: sampler;
+@group(0) @binding(3)
+var<uniform> transition: TransitionUniform;
+
+// a single triangle covering the whole screen
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let uv = vec2<f32>(
+        f32((vertex_index << 1u) & 2u),
+        f32(vertex_index & 2u),
+    );
+
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let outgoing = textureSample(t_outgoing, s_screen, in.uv);
+    let incoming = textureSample(t_incoming, s_screen, in.uv);
+    let t = transition.progress;
+
+    switch transition.effect {
+        // fade through a colour
+        case 0u: {
+            if t < 0.5 {
+                return mix(outgoing, transition.color, t * 2.0);
+            }
+
+            return mix(transition.color, incoming, t * 2.0 - 1.0);
+        }
+        // crossfade
+        case 1u: {
+            return mix(outgoing, incoming, t);
+        }
+        // wipe
+        default: {
+            let along = dot(in.uv - vec2<f32>(0.5), transition.direction) + 0.5;
+            return select(outgoing, incoming, along < t);
+        }
+    }
+}
@@ -1,14 +1,19 @@
 use std::{ffi::OsStr, path::Path};
 
-pub fn load_bytes(file_name: &str) -> Vec<u8> {
+use log::error;
+
+use crate::error::{Error, Result};
+
+pub fn load_bytes(file_name: &str) -> Result<Vec<u8>> {
     let mut path = std::env::current_dir()
-        .expect("Current working directory is not valid");
+        .map_err(|source| Error::Io { path: file_name.into(), source })?;
 
     path.push("res");
     path.push(file_name);
 
-    std::fs::read(path)
-        .expect("Could not read given path!")
+    std::fs::read(&path)
+        .map_err(|source| Error::Io { path, source })
+        .inspect_err(|err| error!("{}", err))
 }
 
 pub fn get_extension(file_name: &str) -> Option<&str> {
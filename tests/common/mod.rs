@@ -0,0 +1,33 @@
+// shared by the test crates, which each use only some of it
+#![allow(dead_code)]
+
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+use wxpg::{app::App, engine_config::EngineConfig, modules::scene_server::SceneRegistry, wgpu, Engine};
+
+// a component the engine does not register by itself
+#[derive(Component, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Health {
+    pub current: u32,
+    pub max: u32,
+}
+
+pub fn registry() -> SceneRegistry {
+    let mut registry = SceneRegistry::default();
+    registry.register::<Health>("Health");
+    registry
+}
+
+// ticks every 0.1 seconds. fails on machines without any adapter,
+// rather than passing without checking anything
+pub fn headless_engine(app: &mut impl App) -> Engine<'_> {
+    let engine_config = EngineConfig::default()
+        .with_size(64, 64)
+        // includes gl, as ci machines often only have a software adapter
+        .with_backends(wgpu::Backends::all())
+        .with_tick_rate(10.0)
+        .unwrap();
+
+    Engine::headless(app, engine_config)
+        .expect("Headless tests need a gpu or a software adapter")
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use std::{cell::Cell, rc::Rc};
+
+use wxpg::{app::App, modules::{commands::Commands, screen_server::ScreenServer, states::States}, screens::screen::Screen};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum GameState {
+    Game,
+}
+
+impl States for GameState {}
+
+struct Scored;
+
+#[derive(Default)]
+struct Counts {
+    sent: Cell<usize>,
+    read_in_updates: Cell<usize>,
+    read_in_frames: Cell<usize>,
+}
+
+struct GameScreen {
+    counts: Rc<Counts>,
+}
+
+impl Screen for GameScreen {
+    fn update(&mut self, commands: &mut Commands) {
+        let read = commands.read::<Scored>().len();
+        self.counts.read_in_updates.set(self.counts.read_in_updates.get() + read);
+
+        commands.send(Scored);
+        self.counts.sent.set(self.counts.sent.get() + 1);
+    }
+
+    fn ui(&mut self, commands: &mut Commands) {
+        let read = commands.read::<Scored>().len();
+        self.counts.read_in_frames.set(self.counts.read_in_frames.get() + read);
+    }
+}
+
+struct EventsApp {
+    counts: Rc<Counts>,
+}
+
+impl App for EventsApp {
+    fn start(&mut self, screen_server: &mut ScreenServer) {
+        let counts = self.counts.clone();
+        screen_server.register_screen(GameScreen { counts }, GameState::Game);
+    }
+}
+
+#[test]
+fn frames_and_ticks_read_every_event_once() {
+    let counts = Rc::new(Counts::default());
+    let mut app = EventsApp { counts: counts.clone() };
+    let mut engine = common::headless_engine(&mut app);
+
+    // several frames per tick, then several ticks per frame
+    for _ in 0..8 {
+        engine.advance(0.05);
+    }
+
+    for _ in 0..4 {
+        engine.advance(0.2);
+    }
+
+    engine.shutdown();
+
+    // the last event only becomes readable on the next tick
+    let sent = counts.sent.get();
+    assert_eq!(sent, 12);
+    assert_eq!(counts.read_in_updates.get(), sent - 1);
+    assert_eq!(counts.read_in_frames.get(), sent - 1);
+}
@@ -0,0 +1,85 @@
+use bevy_ecs::{prelude::*, schedule::ExecutorKind};
+use cgmath::{InnerSpace, Vector3};
+use wxpg::{components::{kinematics::{kinematics_systems, Acceleration, Velocity}, transform::Transform}, modules::time::Time};
+
+fn world(fixed_delta: f32) -> World {
+    let mut world = World::default();
+    world.insert_resource(Time::new(fixed_delta));
+    world
+}
+
+fn schedule() -> Schedule {
+    let mut schedule = Schedule::default();
+    schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+    schedule.add_systems(kinematics_systems());
+    schedule
+}
+
+// runs one second worth of ticks
+fn speed_after_a_second(fixed_delta: f32, ticks: usize) -> f32 {
+    let mut world = world(fixed_delta);
+    let mut schedule = schedule();
+
+    let entity = world.spawn((
+        Transform::default(),
+        Velocity::new(Vector3::new(10.0, 0.0, 0.0)).with_damping(0.5),
+    )).id();
+
+    for _ in 0..ticks {
+        schedule.run(&mut world);
+    }
+
+    world.get::<Velocity>(entity)
+        .unwrap()
+        .linear
+        .magnitude()
+}
+
+#[test]
+fn damping_is_applied_per_second() {
+    let fast_ticks = speed_after_a_second(0.1, 10);
+    let slow_ticks = speed_after_a_second(0.5, 2);
+
+    assert!((fast_ticks - 5.0).abs() < 1e-4);
+    assert!((slow_ticks - 5.0).abs() < 1e-4);
+}
+
+#[test]
+fn speed_is_clamped_to_the_max_speed() {
+    let mut world = world(0.5);
+    let mut schedule = schedule();
+
+    let entity = world.spawn((
+        Transform::default(),
+        Velocity::new(Vector3::new(0.0, 6.0, 8.0)).with_max_speed(5.0),
+    )).id();
+
+    schedule.run(&mut world);
+
+    let velocity = world.get::<Velocity>(entity).unwrap();
+    assert!((velocity.linear - Vector3::new(0.0, 3.0, 4.0)).magnitude() < 1e-5);
+
+    let transform = world.get::<Transform>(entity).unwrap();
+    assert!((transform.translation - Vector3::new(0.0, 1.5, 2.0)).magnitude() < 1e-5);
+}
+
+#[test]
+fn acceleration_is_applied_before_integrating() {
+    let mut world = world(0.1);
+    let mut schedule = schedule();
+
+    let entity = world.spawn((
+        Transform::default(),
+        Velocity::default(),
+        Acceleration(Vector3::new(0.0, 0.0, 10.0)),
+    )).id();
+
+    schedule.run(&mut world);
+
+    let velocity = world.get::<Velocity>(entity).unwrap();
+    assert!((velocity.linear - Vector3::new(0.0, 0.0, 1.0)).magnitude() < 1e-5);
+
+    // moved during the same tick the velocity was gained
+    let transform = world.get::<Transform>(entity).unwrap();
+    assert!((transform.translation - Vector3::new(0.0, 0.0, 0.1)).magnitude() < 1e-5);
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use bevy_ecs::prelude::*;
+use cgmath::Vector3;
+use serde_json::json;
+use wxpg::{components::{hierarchy::Parent, prefab::PrefabInstance, renderable::MeshRef, transform::Transform}, error::Error, modules::prefab::{Prefab, PrefabOverrides}};
+
+use common::{registry, Health};
+
+const ENEMY: &str = r#"{ "entities": [
+    { "components": { "MeshRef": { "file_name": "enemy.obj" }, "Health": { "current": 10, "max": 10 } } },
+    { "components": { "MeshRef": { "file_name": "sword.obj" }, "Transform": { "translation": [0.5, 1.0, 0.0] } } }
+] }"#;
+
+#[test]
+fn instances_are_linked_and_attached_to_the_root() {
+    let prefab = Prefab::from_json("enemy.prefab", ENEMY).unwrap();
+    let mut world = World::default();
+
+    let root = prefab.instantiate(&mut world,
+        &registry(),
+        Transform::from_xyz(3.0, 0.0, 0.0),
+        &PrefabOverrides::default()
+    ).unwrap();
+
+    assert_eq!(world.get::<PrefabInstance>(root), Some(&PrefabInstance::new("enemy.prefab")));
+    assert_eq!(world.get::<Transform>(root), Some(&Transform::from_xyz(3.0, 0.0, 0.0)));
+
+    let mut weapons = world.query::<(&Parent, &MeshRef)>();
+    let (parent, mesh_ref) = weapons.single(&world);
+    assert_eq!(parent.get(), root);
+    assert_eq!(mesh_ref, &MeshRef::new("sword.obj"));
+}
+
+#[test]
+fn overrides_replace_only_the_given_fields() {
+    let prefab = Prefab::from_json("enemy.prefab", ENEMY).unwrap();
+    let mut world = World::default();
+
+    let overrides = PrefabOverrides::default()
+        .with(0, "Health", json!({ "max": 20 }));
+
+    let root = prefab.instantiate(&mut world, &registry(), Transform::default(), &overrides)
+        .unwrap();
+    assert_eq!(world.get::<Health>(root), Some(&Health { current: 10, max: 20 }));
+
+    let overrides = PrefabOverrides::default()
+        .with(5, "Health", json!({ "max": 20 }));
+
+    let result = prefab.instantiate(&mut world, &registry(), Transform::default(), &overrides);
+    assert!(matches!(result, Err(Error::PrefabEntity { idx: 5, .. })));
+}
+
+#[test]
+fn root_transform_overrides_are_merged_into_the_spawn_transform() {
+    let prefab = Prefab::from_json("enemy.prefab", ENEMY).unwrap();
+    let mut world = World::default();
+
+    let overrides = PrefabOverrides::default()
+        .with(0, "Transform", json!({ "scale": [2.0, 2.0, 2.0] }));
+
+    let root = prefab.instantiate(&mut world, &registry(), Transform::from_xyz(3.0, 0.0, 0.0), &overrides)
+        .unwrap();
+
+    let expected = Transform::from_xyz(3.0, 0.0, 0.0)
+        .with_scale(Vector3::new(2.0, 2.0, 2.0));
+    assert_eq!(world.get::<Transform>(root), Some(&expected));
+}
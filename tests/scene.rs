@@ -0,0 +1,130 @@
+mod common;
+
+use bevy_ecs::prelude::*;
+use cgmath::{Deg, Quaternion, Rotation3, Vector3};
+use wxpg::{components::{hierarchy::Parent, kinematics::Velocity, renderable::{MaterialRef, MeshHandle, MeshRef, Visibility}, transform::{GlobalTransform, Transform}}, error::Error, modules::scene_server::{Scene, SceneRegistry, SceneServer}};
+
+use common::{registry, Health};
+
+fn cart_world() -> World {
+    let mut world = World::default();
+
+    let cart = world.spawn((
+        Transform::from_xyz(1.0, 2.0, 3.0)
+            .with_rotation(Quaternion::from_angle_y(Deg(90.0))),
+        MeshRef::new("cart.obj"),
+        MaterialRef::new("cart.png"),
+        Velocity::new(Vector3::new(0.0, 0.0, 1.0)).with_max_speed(4.0),
+        Health { current: 7, max: 10 },
+    )).id();
+
+    world.spawn((
+        Transform::from_xyz(0.5, 0.0, 0.0),
+        MeshRef::new("cart.obj").with_mesh_index(1),
+        Visibility::Hidden,
+        Parent(cart),
+    ));
+
+    world
+}
+
+#[test]
+fn round_trip_keeps_components_and_hierarchy() {
+    let registry = registry();
+    let world = cart_world();
+
+    let json = Scene::from_world(&world, &registry)
+        .and_then(|scene| scene.to_json())
+        .unwrap();
+
+    let mut loaded_world = World::default();
+    let entities = Scene::from_json(&json)
+        .and_then(|scene| scene.spawn(&mut loaded_world, &registry))
+        .unwrap();
+
+    assert_eq!(entities.len(), 2);
+    let (cart, wheel) = (entities[0], entities[1]);
+
+    let original_cart = world.iter_entities()
+        .find(|entity| entity.contains::<Health>())
+        .unwrap();
+
+    assert_eq!(loaded_world.get::<Transform>(cart), original_cart.get::<Transform>());
+    assert_eq!(loaded_world.get::<Velocity>(cart), original_cart.get::<Velocity>());
+    assert_eq!(loaded_world.get::<Health>(cart), Some(&Health { current: 7, max: 10 }));
+    assert_eq!(loaded_world.get::<MaterialRef>(cart), Some(&MaterialRef::new("cart.png")));
+
+    assert_eq!(loaded_world.get::<Parent>(wheel), Some(&Parent(cart)));
+    assert_eq!(loaded_world.get::<Visibility>(wheel), Some(&Visibility::Hidden));
+    assert_eq!(loaded_world.get::<MeshRef>(wheel), Some(&MeshRef::new("cart.obj").with_mesh_index(1)));
+
+    let saved_again = Scene::from_world(&loaded_world, &registry)
+        .and_then(|scene| scene.to_json())
+        .unwrap();
+
+    assert_eq!(json, saved_again);
+}
+
+#[test]
+fn gpu_resources_are_not_saved_or_created() {
+    let scene_server = SceneServer::default();
+    let mut world = cart_world();
+    world.spawn((Transform::default(), MeshHandle(3), GlobalTransform::default()));
+
+    let json = scene_server.save(&world).unwrap();
+    assert!(!json.contains("MeshHandle"));
+    assert!(!json.contains("GlobalTransform"));
+
+    let mut loaded_world = World::default();
+    let entities = scene_server.load(&mut loaded_world, &json).unwrap();
+
+    assert_eq!(entities.len(), 3);
+    assert!(entities.iter().all(|entity| loaded_world.get::<MeshHandle>(*entity).is_none()));
+}
+
+#[test]
+fn unregistered_components_are_skipped() {
+    let json = Scene::from_world(&cart_world(), &registry())
+        .and_then(|scene| scene.to_json())
+        .unwrap();
+
+    let mut world = World::default();
+    let entities = Scene::from_json(&json)
+        .and_then(|scene| scene.spawn(&mut world, &SceneRegistry::default()))
+        .unwrap();
+
+    assert!(world.get::<Health>(entities[0]).is_none());
+    assert!(world.get::<Transform>(entities[0]).is_some());
+}
+
+#[test]
+fn missing_fields_use_defaults() {
+    let json = r#"{ "entities": [{ "components": { "Transform": { "translation": [1.0, 2.0, 3.0] } } }] }"#;
+
+    let mut world = World::default();
+    let entities = SceneServer::default()
+        .load(&mut world, json)
+        .unwrap();
+
+    let transform = world.get::<Transform>(entities[0]).unwrap();
+    assert_eq!(*transform, Transform::from_xyz(1.0, 2.0, 3.0));
+}
+
+#[test]
+fn invalid_scenes_spawn_nothing() {
+    let scene_server = SceneServer::default();
+    let mut world = World::default();
+
+    let missing_parent = r#"{ "entities": [{ "parent": 4, "components": {} }] }"#;
+    let result = scene_server.load(&mut world, missing_parent);
+    assert!(matches!(result, Err(Error::SceneParent(4))));
+
+    let invalid_component = r#"{ "entities": [
+        { "components": { "Transform": {} } },
+        { "components": { "Visibility": "Invisible" } }
+    ] }"#;
+    let result = scene_server.load(&mut world, invalid_component);
+    assert!(matches!(result, Err(Error::SceneComponent { .. })));
+
+    assert_eq!(world.entities().len(), 0);
+}
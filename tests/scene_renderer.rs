@@ -0,0 +1,64 @@
+mod common;
+
+use std::sync::Arc;
+
+use cgmath::Vector3;
+use wxpg::{app::App, components::{renderable::MeshHandle, transform::{GlobalTransform, Transform}}, modules::{screen_server::ScreenServer, states::States}, primitives::cube::Cube, render::texture::Texture, screens::screen::Screen, EngineInternal};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum GameState {
+    Game,
+}
+
+impl States for GameState {}
+
+struct GameScreen;
+
+impl Screen for GameScreen {}
+
+struct CubeApp;
+
+impl App for CubeApp {
+    fn start(&mut self, screen_server: &mut ScreenServer) {
+        screen_server.register_screen(GameScreen, GameState::Game);
+    }
+
+    fn on_engine_ready(&mut self, engine_internal: &mut EngineInternal) {
+        let device = &engine_internal.device;
+        let image = image::DynamicImage::new_rgba8(1, 1);
+        let texture = Texture::from_image(device, &engine_internal.queue, &image, "white.png");
+
+        let render_storage = &mut engine_internal.render_storage;
+        render_storage.push_material(Arc::new(texture), device);
+        render_storage.push_mesh(&Cube::default(), device);
+    }
+}
+
+#[test]
+fn entities_spawned_while_paused_are_prepared() {
+    let mut app = CubeApp;
+    let mut engine = common::headless_engine(&mut app);
+
+    engine.advance(0.1);
+    engine.engine_internal().time.pause();
+
+    let mesh_id = engine.engine_internal().render_storage.meshes()[0].mesh_id();
+    let entity = engine.engine_internal().world
+        .spawn((Transform::from_xyz(1.0, 2.0, 3.0), MeshHandle(mesh_id)))
+        .id();
+
+    engine.advance(0.1);
+    assert_eq!(engine.engine_internal().time.tick_count(), 1);
+    assert_eq!(engine.engine_internal().scene_renderer.instance_count(), 1);
+
+    engine.engine_internal().world
+        .get_mut::<Transform>(entity)
+        .unwrap()
+        .translation = Vector3::new(4.0, 5.0, 6.0);
+
+    engine.advance(0.1);
+    let global_transform = engine.engine_internal().world.get::<GlobalTransform>(entity).unwrap();
+    assert_eq!(global_transform.translation(), Vector3::new(4.0, 5.0, 6.0));
+
+    engine.shutdown();
+}
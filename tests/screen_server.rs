@@ -0,0 +1,180 @@
+mod common;
+
+use std::{cell::RefCell, rc::Rc};
+
+use wxpg::{app::App, modules::{commands::Commands, screen_server::ScreenServer, states::States}, screens::screen::Screen, Engine};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum GameState {
+    A,
+    B,
+    C,
+}
+
+impl States for GameState {}
+
+#[derive(Debug, Clone, Copy)]
+enum Request {
+    Set(GameState),
+    Push(GameState),
+    Pop,
+    Replace(GameState),
+}
+
+// the requests are made by the topmost screen on its next update
+#[derive(Default)]
+struct Shared {
+    log: RefCell<Vec<String>>,
+    requests: RefCell<Vec<Request>>,
+}
+
+impl Shared {
+    fn take_log(&self) -> Vec<String> {
+        self.log.take()
+    }
+}
+
+struct LogScreen {
+    state: GameState,
+    shared: Rc<Shared>,
+}
+
+impl LogScreen {
+    fn log(&self, cycle: &str) {
+        self.shared.log
+            .borrow_mut()
+            .push(format!("{} {:?}", cycle, self.state));
+    }
+}
+
+impl Screen for LogScreen {
+    fn init(&mut self, _commands: &mut Commands) {
+        self.log("init");
+    }
+
+    fn on_enter(&mut self, _commands: &mut Commands) {
+        self.log("enter");
+    }
+
+    fn on_exit(&mut self, _commands: &mut Commands) {
+        self.log("exit");
+    }
+
+    fn on_pause(&mut self, _commands: &mut Commands) {
+        self.log("pause");
+    }
+
+    fn on_resume(&mut self, _commands: &mut Commands) {
+        self.log("resume");
+    }
+
+    fn update(&mut self, commands: &mut Commands) {
+        for request in self.shared.requests.take() {
+            match request {
+                Request::Set(state) => commands.set_state(state),
+                Request::Push(state) => commands.push_state(state),
+                Request::Pop => commands.pop_state(),
+                Request::Replace(state) => commands.replace_state(state),
+            }
+        }
+    }
+}
+
+struct LogApp {
+    shared: Rc<Shared>,
+}
+
+impl App for LogApp {
+    fn start(&mut self, screen_server: &mut ScreenServer) {
+        for state in [GameState::A, GameState::B, GameState::C] {
+            let shared = self.shared.clone();
+            screen_server.register_screen(LogScreen { state, shared }, state);
+        }
+    }
+}
+
+fn log_app() -> (LogApp, Rc<Shared>) {
+    let shared = Rc::new(Shared::default());
+    let app = LogApp { shared: shared.clone() };
+
+    (app, shared)
+}
+
+// runs one tick, in which the requests are made, and the frame that applies them
+fn request(engine: &mut Engine, shared: &Shared, requests: &[Request]) -> Vec<String> {
+    shared.requests.replace(requests.to_vec());
+    engine.advance(0.1);
+    shared.take_log()
+}
+
+#[test]
+fn init_runs_once_and_exits_come_before_enters() {
+    let (mut app, shared) = log_app();
+    let mut engine = common::headless_engine(&mut app);
+
+    engine.advance(0.1);
+    assert_eq!(shared.take_log(), ["init A", "enter A"]);
+
+    let log = request(&mut engine, &shared, &[Request::Set(GameState::B)]);
+    assert_eq!(log, ["exit A", "init B", "enter B"]);
+
+    let log = request(&mut engine, &shared, &[Request::Set(GameState::A)]);
+    assert_eq!(log, ["exit B", "enter A"]);
+
+    engine.shutdown();
+}
+
+#[test]
+fn push_and_pop_pause_and_resume_the_state_beneath() {
+    let (mut app, shared) = log_app();
+    let mut engine = common::headless_engine(&mut app);
+
+    engine.advance(0.1);
+    shared.take_log();
+
+    let log = request(&mut engine, &shared, &[Request::Push(GameState::B)]);
+    assert_eq!(log, ["pause A", "init B", "enter B"]);
+
+    let log = request(&mut engine, &shared, &[Request::Pop]);
+    assert_eq!(log, ["exit B", "resume A"]);
+    assert!(engine.screen_server().is_state(GameState::A));
+
+    engine.shutdown();
+}
+
+#[test]
+fn replace_exits_the_top_and_set_exits_everything() {
+    let (mut app, shared) = log_app();
+    let mut engine = common::headless_engine(&mut app);
+
+    engine.advance(0.1);
+    request(&mut engine, &shared, &[Request::Push(GameState::B)]);
+
+    let log = request(&mut engine, &shared, &[Request::Replace(GameState::C)]);
+    assert_eq!(log, ["exit B", "init C", "enter C"]);
+    assert_eq!(engine.screen_server().stack().len(), 2);
+
+    let log = request(&mut engine, &shared, &[Request::Set(GameState::B)]);
+    assert_eq!(log, ["exit C", "exit A", "enter B"]);
+    assert_eq!(engine.screen_server().stack().len(), 1);
+
+    engine.shutdown();
+}
+
+#[test]
+fn transitions_queued_together_are_all_applied() {
+    let (mut app, shared) = log_app();
+    let mut engine = common::headless_engine(&mut app);
+
+    engine.advance(0.1);
+    shared.take_log();
+
+    let requests = [Request::Push(GameState::B), Request::Push(GameState::C), Request::Pop];
+    let log = request(&mut engine, &shared, &requests);
+
+    assert_eq!(log, ["pause A", "init B", "enter B", "pause B", "init C", "enter C", "exit C", "resume B"]);
+    assert!(engine.screen_server().is_state(GameState::B));
+    assert_eq!(engine.screen_server().stack().len(), 2);
+
+    engine.shutdown();
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use wxpg::{app::App, modules::{commands::Commands, screen_server::ScreenServer, states::States, timer_server::TimerDuration}, screens::screen::Screen};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum GameState {
+    Paused,
+    Done,
+}
+
+impl States for GameState {}
+
+struct PausedScreen;
+
+impl Screen for PausedScreen {
+    fn init(&mut self, commands: &mut Commands) {
+        commands.time_mut().pause();
+        commands.after(TimerDuration::Seconds(0.25), |commands| commands.set_state(GameState::Done));
+    }
+}
+
+struct DoneScreen;
+
+impl Screen for DoneScreen {}
+
+struct TimerApp;
+
+impl App for TimerApp {
+    fn start(&mut self, screen_server: &mut ScreenServer) {
+        screen_server.register_screen(PausedScreen, GameState::Paused);
+        screen_server.register_screen(DoneScreen, GameState::Done);
+    }
+}
+
+#[test]
+fn second_timers_fire_in_headless_frames_while_paused() {
+    let mut app = TimerApp;
+    let mut engine = common::headless_engine(&mut app);
+
+    for _ in 0..3 {
+        engine.advance(0.1);
+    }
+
+    assert!(engine.screen_server().is_state(GameState::Paused));
+
+    engine.advance(0.1);
+    assert!(engine.screen_server().is_state(GameState::Done));
+    assert_eq!(engine.engine_internal().time.tick_count(), 1);
+
+    engine.shutdown();
+}
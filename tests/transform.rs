@@ -0,0 +1,103 @@
+use bevy_ecs::{prelude::*, schedule::ExecutorKind};
+use cgmath::{Matrix4, SquareMatrix, Vector3};
+use wxpg::components::{hierarchy::{Children, Parent}, transform::{transform_systems, GlobalTransform, Transform}};
+
+fn schedule() -> Schedule {
+    let mut schedule = Schedule::default();
+    schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+    schedule.add_systems(transform_systems());
+    schedule
+}
+
+fn translation(world: &World, entity: Entity) -> Vector3<f32> {
+    world.get::<GlobalTransform>(entity)
+        .unwrap()
+        .translation()
+}
+
+#[test]
+fn children_are_placed_relative_to_their_parents() {
+    let mut world = World::default();
+    let mut schedule = schedule();
+
+    let root = world.spawn(Transform::from_xyz(1.0, 0.0, 0.0)).id();
+    let child = world.spawn((Transform::from_xyz(0.0, 2.0, 0.0), Parent(root))).id();
+    let grandchild = world.spawn((Transform::from_xyz(0.0, 0.0, 3.0), Parent(child))).id();
+
+    schedule.run(&mut world);
+
+    assert_eq!(translation(&world, root), Vector3::new(1.0, 0.0, 0.0));
+    assert_eq!(translation(&world, child), Vector3::new(1.0, 2.0, 0.0));
+    assert_eq!(translation(&world, grandchild), Vector3::new(1.0, 2.0, 3.0));
+    assert_eq!(world.get::<Children>(root).unwrap().entities(), &[child]);
+}
+
+#[test]
+fn reparenting_moves_the_child() {
+    let mut world = World::default();
+    let mut schedule = schedule();
+
+    let first = world.spawn(Transform::from_xyz(1.0, 0.0, 0.0)).id();
+    let second = world.spawn(Transform::from_xyz(5.0, 0.0, 0.0)).id();
+    let child = world.spawn((Transform::from_xyz(0.0, 1.0, 0.0), Parent(first))).id();
+
+    schedule.run(&mut world);
+    assert_eq!(translation(&world, child), Vector3::new(1.0, 1.0, 0.0));
+
+    world.entity_mut(child).insert(Parent(second));
+    schedule.run(&mut world);
+
+    assert_eq!(translation(&world, child), Vector3::new(5.0, 1.0, 0.0));
+    assert!(world.get::<Children>(first).is_none());
+    assert_eq!(world.get::<Children>(second).unwrap().entities(), &[child]);
+}
+
+#[test]
+fn removing_the_parent_makes_a_root() {
+    let mut world = World::default();
+    let mut schedule = schedule();
+
+    let parent = world.spawn(Transform::from_xyz(1.0, 0.0, 0.0)).id();
+    let child = world.spawn((Transform::from_xyz(0.0, 1.0, 0.0), Parent(parent))).id();
+
+    schedule.run(&mut world);
+    world.entity_mut(child).remove::<Parent>();
+    schedule.run(&mut world);
+
+    assert_eq!(translation(&world, child), Vector3::new(0.0, 1.0, 0.0));
+    assert!(world.get::<Children>(parent).is_none());
+}
+
+#[test]
+fn children_of_despawned_parents_become_roots() {
+    let mut world = World::default();
+    let mut schedule = schedule();
+
+    let parent = world.spawn(Transform::from_xyz(1.0, 0.0, 0.0)).id();
+    let child = world.spawn((Transform::from_xyz(0.0, 1.0, 0.0), Parent(parent))).id();
+
+    schedule.run(&mut world);
+    world.despawn(parent);
+    schedule.run(&mut world);
+
+    assert_eq!(translation(&world, child), Vector3::new(0.0, 1.0, 0.0));
+}
+
+#[test]
+fn cycles_are_never_propagated() {
+    let mut world = World::default();
+    let mut schedule = schedule();
+
+    let first = world.spawn(Transform::from_xyz(1.0, 0.0, 0.0)).id();
+    let second = world.spawn((Transform::from_xyz(0.0, 1.0, 0.0), Parent(first))).id();
+    world.entity_mut(first).insert(Parent(second));
+
+    let root = world.spawn(Transform::from_xyz(0.0, 0.0, 1.0)).id();
+
+    schedule.run(&mut world);
+
+    // neither entity is a root, so both keep the default global transform
+    assert_eq!(world.get::<GlobalTransform>(first).unwrap().matrix(), Matrix4::identity());
+    assert_eq!(world.get::<GlobalTransform>(second).unwrap().matrix(), Matrix4::identity());
+    assert_eq!(translation(&world, root), Vector3::new(0.0, 0.0, 1.0));
+}
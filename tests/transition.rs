@@ -0,0 +1,51 @@
+mod common;
+
+use wxpg::{app::App, modules::{commands::Commands, screen_server::ScreenServer, screen_transition::ScreenTransition, states::States}, screens::screen::Screen};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum GameState {
+    Menu,
+    Game,
+}
+
+impl States for GameState {}
+
+struct MenuScreen;
+
+impl Screen for MenuScreen {
+    fn update(&mut self, commands: &mut Commands) {
+        commands.set_state_with(GameState::Game, ScreenTransition::crossfade(0.5));
+    }
+}
+
+struct GameScreen;
+
+impl Screen for GameScreen {}
+
+struct TransitionApp;
+
+impl App for TransitionApp {
+    fn start(&mut self, screen_server: &mut ScreenServer) {
+        screen_server.register_screen(MenuScreen, GameState::Menu);
+        screen_server.register_screen(GameScreen, GameState::Game);
+    }
+}
+
+#[test]
+fn transitions_complete_when_advanced_headless() {
+    let mut app = TransitionApp;
+    let mut engine = common::headless_engine(&mut app);
+
+    engine.advance(0.1);
+    assert!(engine.screen_server().is_transitioning());
+    assert!(engine.screen_server().is_state(GameState::Game));
+
+    for _ in 0..5 {
+        engine.advance(0.1);
+    }
+
+    assert!(!engine.screen_server().is_transitioning());
+    assert!((engine.engine_internal().time.elapsed() - 0.6).abs() < 1e-5);
+
+    engine.shutdown();
+}